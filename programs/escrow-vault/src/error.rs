@@ -86,4 +86,52 @@ pub enum VaultError {
     
     #[msg("CPI caller detection failed")]
     CpiCallerDetectionFailed,
+    
+    #[msg("Reward pool underfunded")]
+    RewardPoolUnderfunded,
+    
+    #[msg("Invalid pending admin")]
+    InvalidPendingAdmin,
+    
+    #[msg("No pending admin transfer")]
+    NoPendingAdmin,
+    
+    #[msg("Unsupported token mint extension")]
+    UnsupportedMintExtension,
+    
+    #[msg("Collateral mint is not enabled")]
+    CollateralMintDisabled,
+    
+    #[msg("Deposit below collateral minimum")]
+    DepositBelowMinimum,
+    
+    #[msg("Collateral deposit cap exceeded")]
+    DepositCapExceeded,
+    
+    #[msg("Invalid collateral parameters")]
+    InvalidCollateralParameters,
+    
+    #[msg("Collateral mint decimals changed")]
+    CollateralDecimalsMismatch,
+    
+    #[msg("Vault reserves do not cover liabilities")]
+    ReservesInsufficient,
+    
+    #[msg("Insufficient locked balance")]
+    InsufficientLockedBalance,
+    
+    #[msg("Account already migrated")]
+    AlreadyMigrated,
+    
+    #[msg("Invalid trader operations")]
+    InvalidTraderOperations,
+    
+    #[msg("Operation not permitted for trader program")]
+    TraderOperationNotPermitted,
+    
+    #[msg("Invalid outflow limit parameters")]
+    InvalidOutflowLimit,
+    
+    #[msg("Trader outflow limit exceeded")]
+    OutflowLimitExceeded,
 } 
\ No newline at end of file
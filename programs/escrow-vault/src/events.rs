@@ -27,8 +27,10 @@ pub struct VaultUnpaused {
 pub struct CollateralDeposited {
     pub user: Pubkey,
     pub token_mint: Pubkey,
-    pub amount: u64,
-    pub new_balance: u64,
+    pub amount: u64,            // Net amount received and credited
+    pub transfer_fee: u64,      // Token-2022 transfer fee withheld (0 for SPL Token)
+    pub new_balance: u64,       // Free balance after deposit
+    pub locked_balance: u64,
 }
 
 /// Collateral withdrawn by user
@@ -37,24 +39,51 @@ pub struct CollateralWithdrawn {
     pub user: Pubkey,
     pub token_mint: Pubkey,
     pub amount: u64,
-    pub remaining_balance: u64,
+    pub remaining_balance: u64, // Free balance after withdrawal
+    pub locked_balance: u64,
 }
 
-/// Balance slashed (locked) via CPI
+/// Balance slashed (subtracted) via CPI
 #[event]
 pub struct BalanceSlashed {
     pub user: Pubkey,
     pub token_mint: Pubkey,
     pub amount: u64,
+    pub free_balance: u64,
+    pub locked_balance: u64,
     pub caller_program: Pubkey,
 }
 
-/// Balance credited (unlocked) via CPI
+/// Balance credited (added) via CPI
 #[event]
 pub struct BalanceCredited {
     pub user: Pubkey,
     pub token_mint: Pubkey,
     pub amount: u64,
+    pub free_balance: u64,
+    pub locked_balance: u64,
+    pub caller_program: Pubkey,
+}
+
+/// Free balance locked for an open trade via CPI
+#[event]
+pub struct BalanceLocked {
+    pub user: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub free_balance: u64,
+    pub locked_balance: u64,
+    pub caller_program: Pubkey,
+}
+
+/// Locked balance released back to free balance via CPI
+#[event]
+pub struct BalanceUnlocked {
+    pub user: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub free_balance: u64,
+    pub locked_balance: u64,
     pub caller_program: Pubkey,
 }
 
@@ -65,6 +94,8 @@ pub struct TokensTransferredOut {
     pub recipient: Pubkey,
     pub token_mint: Pubkey,
     pub amount: u64,
+    pub free_balance: u64,
+    pub locked_balance: u64,
     pub caller_program: Pubkey,
 }
 
@@ -75,6 +106,10 @@ pub struct BalanceTransferred {
     pub to_user: Pubkey,
     pub token_mint: Pubkey,
     pub amount: u64,
+    pub from_free_balance: u64,
+    pub from_locked_balance: u64,
+    pub to_free_balance: u64,
+    pub to_locked_balance: u64,
     pub caller_program: Pubkey,
 }
 
@@ -82,6 +117,19 @@ pub struct BalanceTransferred {
 #[event]
 pub struct AuthorizedTraderAdded {
     pub trader_program: Pubkey,
+    pub signer: Pubkey,         // ["vault_signer"] PDA the program signs CPIs with
+    pub operations: u8,         // AuthorizedTrader::OP_* bitmask
+    pub total_traders: u32,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Authorized trader operations changed
+#[event]
+pub struct AuthorizedTraderUpdated {
+    pub trader_program: Pubkey,
+    pub previous_operations: u8,
+    pub operations: u8,
     pub admin: Pubkey,
     pub timestamp: i64,
 }
@@ -90,6 +138,148 @@ pub struct AuthorizedTraderAdded {
 #[event]
 pub struct AuthorizedTraderRemoved {
     pub trader_program: Pubkey,
+    pub total_traders: u32,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Per-mint outflow limit of a trader program added / updated
+#[event]
+pub struct TraderOutflowLimitConfigured {
+    pub trader_program: Pubkey,
+    pub token_mint: Pubkey,
+    pub max_per_transfer: u64,  // 0 = unlimited
+    pub window_limit: u64,      // 0 = unlimited
+    pub window_duration: i64,
     pub admin: Pubkey,
     pub timestamp: i64,
-} 
\ No newline at end of file
+}
+
+/// Per-mint outflow limit of a trader program removed
+#[event]
+pub struct TraderOutflowLimitRemoved {
+    pub trader_program: Pubkey,
+    pub token_mint: Pubkey,
+    pub total_outflow: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Reward pool funded by admin
+#[event]
+pub struct RewardPoolFunded {
+    pub token_mint: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u64,            // Net amount received and credited to pool
+    pub transfer_fee: u64,      // Token-2022 transfer fee withheld (0 for SPL Token)
+    pub pool_balance: u64,
+    pub timestamp: i64,
+}
+
+/// Settlement reward paid from reward pool via CPI
+#[event]
+pub struct RewardPaid {
+    pub token_mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub pool_balance: u64,
+    pub caller_program: Pubkey,
+}
+
+/// Admin transfer proposed by current admin
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Admin transfer accepted by pending admin
+#[event]
+pub struct AdminTransferAccepted {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Pending admin transfer cancelled by current admin
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub cancelled_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emergency admin rotation proposed by admin
+#[event]
+pub struct EmergencyAdminTransferProposed {
+    pub admin: Pubkey,
+    pub emergency_admin: Pubkey,
+    pub pending_emergency_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emergency admin rotation accepted by pending emergency admin
+#[event]
+pub struct EmergencyAdminTransferAccepted {
+    pub previous_emergency_admin: Pubkey,
+    pub new_emergency_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Pending emergency admin rotation cancelled by admin
+#[event]
+pub struct EmergencyAdminTransferCancelled {
+    pub admin: Pubkey,
+    pub cancelled_emergency_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Collateral mint allowlist entry added or updated by admin
+#[event]
+pub struct CollateralMintConfigured {
+    pub token_mint: Pubkey,
+    pub enabled: bool,
+    pub deposit_cap: u64,
+    pub min_deposit: u64,
+    pub decimals: u8,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Proof-of-reserves snapshot (permissionless reconcile)
+#[event]
+pub struct ReserveSnapshot {
+    pub token_mint: Pubkey,
+    pub vault_ata: Pubkey,
+    pub reserves: u64,              // Actual vault_ata token amount
+    pub total_deposits: u64,        // User liabilities
+    pub reward_pool_balance: u64,   // Protocol-funded reward liabilities
+    pub surplus: u64,               // reserves - liabilities (0 if deficit)
+    pub deficit: u64,               // liabilities - reserves (0 if surplus)
+    pub timestamp: i64,
+}
+
+/// UserBalance account migrated to the current layout (free/locked, versioned)
+#[event]
+pub struct UserBalanceMigrated {
+    pub user: Pubkey,
+    pub token_mint: Pubkey,
+    pub free_balance: u64,
+    pub locked_balance: u64,
+    pub previous_version: u8,
+    pub version: u8,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Versioned account upgraded in place by a migrate_* instruction
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub account_type: String,
+    pub previous_version: u8,
+    pub version: u8,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::error::VaultError;
+use crate::events::*;
+use crate::utils::is_supported_mint;
+
+/// Add / update a collateral mint on the allowlist (Admin only)
+#[derive(Accounts)]
+pub struct ConfigureCollateralMint<'info> {
+    #[account(
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ VaultError::InvalidAdmin,
+    )]
+    pub config: Box<Account<'info, VaultConfig>>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + CollateralMintConfig::INIT_SPACE,
+        seeds = [
+            CollateralMintConfig::COLLATERAL_MINT_CONFIG_SEED,
+            token_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub collateral_mint_config: Box<Account<'info, CollateralMintConfig>>,
+    
+    /// Collateral mint (SPL Token or Token-2022)
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ConfigureCollateralMint>,
+    enabled: bool,
+    deposit_cap: u64,
+    min_deposit: u64,
+) -> Result<()> {
+    // Reject Token-2022 mints with extensions the vault cannot custody
+    require!(
+        is_supported_mint(&ctx.accounts.token_mint.to_account_info())?,
+        VaultError::UnsupportedMintExtension
+    );
+    
+    // Validate parameters (cap 0 = uncapped)
+    require!(
+        deposit_cap == 0 || deposit_cap >= min_deposit,
+        VaultError::InvalidCollateralParameters
+    );
+    
+    let collateral_mint_config = &mut ctx.accounts.collateral_mint_config;
+    let token_mint = ctx.accounts.token_mint.key();
+    let decimals = ctx.accounts.token_mint.decimals;
+    
+    // Initialize config if new
+    if collateral_mint_config.token_mint == Pubkey::default() {
+        collateral_mint_config.initialize(
+            token_mint,
+            decimals,
+            ctx.bumps.collateral_mint_config,
+        );
+    }
+    
+    // Update parameters and refresh decimals snapshot
+    collateral_mint_config.enabled = enabled;
+    collateral_mint_config.deposit_cap = deposit_cap;
+    collateral_mint_config.min_deposit = min_deposit;
+    collateral_mint_config.decimals = decimals;
+    collateral_mint_config.updated_at = Clock::get()?.unix_timestamp;
+    
+    // Emit event
+    emit!(CollateralMintConfigured {
+        token_mint,
+        enabled,
+        deposit_cap,
+        min_deposit,
+        decimals,
+        admin: ctx.accounts.admin.key(),
+        timestamp: collateral_mint_config.updated_at,
+    });
+    
+    msg!(
+        "Collateral mint configured: token={}, enabled={}, deposit_cap={}, min_deposit={}, decimals={}",
+        token_mint,
+        enabled,
+        deposit_cap,
+        min_deposit,
+        decimals
+    );
+    
+    Ok(())
+}
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 // AssociatedToken import removed for size optimization
 use crate::state::*;
 use crate::error::VaultError;
 use crate::events::*;
+use crate::utils::is_supported_mint;
 
-/// User deposits collateral tokens (ANY TOKEN SUPPORTED)
+/// User deposits collateral tokens (allowlisted mints only - SPL Token or Token-2022)
 #[derive(Accounts)]
 #[instruction(amount: u64)]
 pub struct DepositCollateral<'info> {
@@ -41,12 +42,24 @@ pub struct DepositCollateral<'info> {
     )]
     pub vault_authority: Box<Account<'info, VaultAuthority>>,
     
+    /// Collateral allowlist entry - mint must be configured and enabled by admin
+    #[account(
+        seeds = [
+            CollateralMintConfig::COLLATERAL_MINT_CONFIG_SEED,
+            token_mint.key().as_ref()
+        ],
+        bump = collateral_mint_config.bump,
+        constraint = collateral_mint_config.enabled @ VaultError::CollateralMintDisabled,
+        constraint = collateral_mint_config.decimals == token_mint.decimals @ VaultError::CollateralDecimalsMismatch,
+    )]
+    pub collateral_mint_config: Box<Account<'info, CollateralMintConfig>>,
+    
     #[account(
         mut,
         constraint = vault_ata.mint == token_mint.key() @ VaultError::InvalidTokenMint,
         constraint = vault_ata.owner == vault_authority.key() @ VaultError::InvalidAccountOwner,
     )]
-    pub vault_ata: Box<Account<'info, TokenAccount>>,
+    pub vault_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
     #[account(
         mut,
@@ -54,17 +67,20 @@ pub struct DepositCollateral<'info> {
         constraint = user_ata.owner == user.key() @ VaultError::InvalidAccountOwner,
         constraint = user_ata.amount >= amount @ VaultError::InsufficientBalance,
     )]
-    pub user_ata: Box<Account<'info, TokenAccount>>,
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// Token mint being deposited (ANY TOKEN SUPPORTED)
-    /// CHECK: Token mint validation is done through user_ata.mint constraint
-    #[account(address = user_ata.mint)]
-    pub token_mint: UncheckedAccount<'info>,
+    /// Token mint being deposited (must be allowlisted)
+    /// Token-2022 extensions are checked in handler
+    #[account(
+        address = user_ata.mint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -72,6 +88,12 @@ pub fn handler(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
     // Validate amount
     require!(amount > 0, VaultError::ZeroAmount);
     
+    // Reject Token-2022 mints with extensions the vault cannot custody
+    require!(
+        is_supported_mint(&ctx.accounts.token_mint.to_account_info())?,
+        VaultError::UnsupportedMintExtension
+    );
+    
     let user_balance = &mut ctx.accounts.user_balance;
     let vault_authority = &mut ctx.accounts.vault_authority;
     let token_mint = ctx.accounts.token_mint.key();
@@ -95,36 +117,55 @@ pub fn handler(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
     }
     
     // Transfer tokens from user to vault
+    let vault_balance_before = ctx.accounts.vault_ata.amount;
     let transfer_cpi = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.user_ata.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
             to: ctx.accounts.vault_ata.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         },
     );
     
-    token::transfer(transfer_cpi, amount)?;
+    token_interface::transfer_checked(transfer_cpi, amount, ctx.accounts.token_mint.decimals)?;
+    
+    // Credit what actually arrived (Token-2022 transfer fee is withheld from the vault side)
+    ctx.accounts.vault_ata.reload()?;
+    let received = ctx.accounts.vault_ata.amount
+        .checked_sub(vault_balance_before)
+        .ok_or(VaultError::MathOverflow)?;
+    require!(received > 0, VaultError::ZeroAmount);
+    let transfer_fee = amount - received;
+    
+    // Enforce allowlist minimum and cap on the credited amount
+    ctx.accounts.collateral_mint_config.validate_deposit(
+        received,
+        vault_authority.total_deposits,
+    )?;
     
     // Add to user balance (exact EVM logic)
-    user_balance.credit_balance(amount)?;
+    user_balance.credit_balance(received)?;
     
     // Add to total deposits (exact EVM logic)
-    vault_authority.add_deposit(amount)?;
+    vault_authority.add_deposit(received)?;
     
     // Emit deposit event
     emit!(CollateralDeposited {
         user: ctx.accounts.user.key(),
         token_mint,
-        amount,
+        amount: received,
+        transfer_fee,
         new_balance: user_balance.balance,
+        locked_balance: user_balance.locked,
     });
     
     msg!(
-        "Collateral deposited: user={}, token={}, amount={}, new_balance={}",
+        "Collateral deposited: user={}, token={}, amount={}, transfer_fee={}, new_balance={}",
         ctx.accounts.user.key(),
         token_mint,
-        amount,
+        received,
+        transfer_fee,
         user_balance.balance
     );
     
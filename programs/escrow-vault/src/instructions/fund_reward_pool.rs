@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::error::VaultError;
+use crate::events::*;
+
+/// Admin funds the settlement reward pool of a collateral mint
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct FundRewardPool<'info> {
+    #[account(
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ VaultError::InvalidAdmin,
+        constraint = !config.paused @ VaultError::VaultPaused,
+    )]
+    pub config: Box<Account<'info, VaultConfig>>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + RewardPool::INIT_SPACE,
+        seeds = [
+            RewardPool::REWARD_POOL_SEED,
+            vault_authority.token_mint.as_ref()
+        ],
+        bump,
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    
+    /// Vault authority must already exist (created by the first deposit)
+    #[account(
+        mut,
+        seeds = [
+            VaultAuthority::VAULT_AUTHORITY_SEED,
+            vault_authority.token_mint.as_ref()
+        ],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Box<Account<'info, VaultAuthority>>,
+    
+    #[account(
+        mut,
+        constraint = vault_ata.key() == vault_authority.vault_ata @ VaultError::InvalidTokenAccount,
+    )]
+    pub vault_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = admin_ata.mint == vault_authority.token_mint @ VaultError::InvalidTokenMint,
+        constraint = admin_ata.owner == admin.key() @ VaultError::InvalidAccountOwner,
+        constraint = admin_ata.amount >= amount @ VaultError::InsufficientBalance,
+    )]
+    pub admin_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Reward token mint (required by transfer_checked)
+    #[account(
+        address = vault_authority.token_mint @ VaultError::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundRewardPool>, amount: u64) -> Result<()> {
+    // Validate amount
+    require!(amount > 0, VaultError::ZeroAmount);
+    
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    let token_mint = ctx.accounts.vault_authority.token_mint;
+    
+    // Initialize reward pool if new
+    if reward_pool.token_mint == Pubkey::default() {
+        reward_pool.initialize(token_mint, ctx.bumps.reward_pool);
+    }
+    
+    // Transfer tokens from admin to vault
+    let vault_balance_before = ctx.accounts.vault_ata.amount;
+    let transfer_cpi = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.admin_ata.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.vault_ata.to_account_info(),
+            authority: ctx.accounts.admin.to_account_info(),
+        },
+    );
+    
+    token_interface::transfer_checked(transfer_cpi, amount, ctx.accounts.token_mint.decimals)?;
+    
+    // Credit the pool with what actually arrived (kept separate from user deposits)
+    ctx.accounts.vault_ata.reload()?;
+    let received = ctx.accounts.vault_ata.amount
+        .checked_sub(vault_balance_before)
+        .ok_or(VaultError::MathOverflow)?;
+    let transfer_fee = amount - received;
+    reward_pool.fund(received)?;
+    ctx.accounts.vault_authority.add_reward_liability(received)?;
+    
+    emit!(RewardPoolFunded {
+        token_mint,
+        admin: ctx.accounts.admin.key(),
+        amount: received,
+        transfer_fee,
+        pool_balance: reward_pool.balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!(
+        "Reward pool funded: token={}, amount={}, transfer_fee={}, pool_balance={}",
+        token_mint,
+        received,
+        transfer_fee,
+        reward_pool.balance
+    );
+    
+    Ok(())
+}
@@ -3,9 +3,11 @@ use crate::state::*;
 use crate::error::VaultError;
 use crate::events::*;
 
-/// Manage authorized trading programs (Admin only)
+/// Authorize a trading program for CPI operations (Admin only)
+/// The program must sign its CPIs with its ["vault_signer"] PDA
 #[derive(Accounts)]
-pub struct ManageAuthorizedTrader<'info> {
+#[instruction(trader_program: Pubkey)]
+pub struct AddAuthorizedTrader<'info> {
     #[account(
         mut,
         seeds = [VaultConfig::VAULT_CONFIG_SEED],
@@ -13,56 +15,297 @@ pub struct ManageAuthorizedTrader<'info> {
         constraint = config.admin == admin.key() @ VaultError::InvalidAdmin,
     )]
     pub config: Account<'info, VaultConfig>,
-    
+
+    /// AuthorizedTrader PDA (init fails if the program is already authorized)
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AuthorizedTrader::INIT_SPACE,
+        seeds = [AuthorizedTrader::AUTHORIZED_TRADER_SEED, trader_program.as_ref()],
+        bump,
+    )]
+    pub authorized_trader: Account<'info, AuthorizedTrader>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Change allowed operations of an authorized trading program (Admin only)
+#[derive(Accounts)]
+pub struct UpdateAuthorizedTrader<'info> {
+    #[account(
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ VaultError::InvalidAdmin,
+    )]
+    pub config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            authorized_trader.trader_program.as_ref()
+        ],
+        bump = authorized_trader.bump,
+    )]
+    pub authorized_trader: Account<'info, AuthorizedTrader>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Revoke a trading program (Admin only, rent refunded to admin)
+/// Outflow limit PDAs of the program are closed separately
+#[derive(Accounts)]
+pub struct RemoveAuthorizedTrader<'info> {
+    #[account(
+        mut,
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ VaultError::InvalidAdmin,
+    )]
+    pub config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            authorized_trader.trader_program.as_ref()
+        ],
+        bump = authorized_trader.bump,
+    )]
+    pub authorized_trader: Account<'info, AuthorizedTrader>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Add / update a per-mint outflow limit of an authorized trading program (Admin only)
+#[derive(Accounts)]
+#[instruction(token_mint: Pubkey)]
+pub struct SetTraderOutflowLimit<'info> {
+    #[account(
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ VaultError::InvalidAdmin,
+    )]
+    pub config: Account<'info, VaultConfig>,
+
+    #[account(
+        seeds = [
+            AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            authorized_trader.trader_program.as_ref()
+        ],
+        bump = authorized_trader.bump,
+    )]
+    pub authorized_trader: Account<'info, AuthorizedTrader>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + TraderOutflowLimit::INIT_SPACE,
+        seeds = [
+            TraderOutflowLimit::TRADER_OUTFLOW_LIMIT_SEED,
+            authorized_trader.trader_program.as_ref(),
+            token_mint.as_ref()
+        ],
+        bump,
+    )]
+    pub outflow_limit: Account<'info, TraderOutflowLimit>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remove a per-mint outflow limit (Admin only, rent refunded to admin)
+#[derive(Accounts)]
+pub struct RemoveTraderOutflowLimit<'info> {
+    #[account(
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ VaultError::InvalidAdmin,
+    )]
+    pub config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            TraderOutflowLimit::TRADER_OUTFLOW_LIMIT_SEED,
+            outflow_limit.trader_program.as_ref(),
+            outflow_limit.token_mint.as_ref()
+        ],
+        bump = outflow_limit.bump,
+    )]
+    pub outflow_limit: Account<'info, TraderOutflowLimit>,
+
+    #[account(mut)]
     pub admin: Signer<'info>,
 }
 
 pub fn add_handler(
-    ctx: Context<ManageAuthorizedTrader>,
+    ctx: Context<AddAuthorizedTrader>,
     trader_program: Pubkey,
+    operations: u8,
 ) -> Result<()> {
+    require!(
+        trader_program != Pubkey::default(),
+        VaultError::UnauthorizedTraderProgram
+    );
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let authorized_trader = &mut ctx.accounts.authorized_trader;
+    authorized_trader.initialize(
+        trader_program,
+        operations,
+        admin,
+        timestamp,
+        ctx.bumps.authorized_trader,
+    )?;
+
     let config = &mut ctx.accounts.config;
-    
-    // Add authorized trader
-    config.add_authorized_trader(trader_program)?;
-    
+    config.increment_trader_count()?;
+
     // Emit event
     emit!(AuthorizedTraderAdded {
         trader_program,
-        admin: ctx.accounts.admin.key(),
-        timestamp: Clock::get()?.unix_timestamp,
+        signer: authorized_trader.signer,
+        operations,
+        total_traders: config.trader_count,
+        admin,
+        timestamp,
     });
-    
+
     msg!(
-        "Authorized trader added: program={}, admin={}",
+        "Authorized trader added: program={}, signer={}, operations={:#08b}, admin={}, total={}",
         trader_program,
-        ctx.accounts.admin.key()
+        authorized_trader.signer,
+        operations,
+        admin,
+        config.trader_count
     );
-    
+
     Ok(())
 }
 
-pub fn remove_handler(
-    ctx: Context<ManageAuthorizedTrader>,
-    trader_program: Pubkey,
+pub fn update_handler(
+    ctx: Context<UpdateAuthorizedTrader>,
+    operations: u8,
 ) -> Result<()> {
+    let authorized_trader = &mut ctx.accounts.authorized_trader;
+    let previous_operations = authorized_trader.operations;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    authorized_trader.set_operations(operations, timestamp)?;
+
+    // Emit event
+    emit!(AuthorizedTraderUpdated {
+        trader_program: authorized_trader.trader_program,
+        previous_operations,
+        operations,
+        admin: ctx.accounts.admin.key(),
+        timestamp,
+    });
+
+    msg!(
+        "Authorized trader updated: program={}, operations={:#08b} -> {:#08b}",
+        authorized_trader.trader_program,
+        previous_operations,
+        operations
+    );
+
+    Ok(())
+}
+
+pub fn remove_handler(ctx: Context<RemoveAuthorizedTrader>) -> Result<()> {
+    let trader_program = ctx.accounts.authorized_trader.trader_program;
+
     let config = &mut ctx.accounts.config;
-    
-    // Remove authorized trader
-    config.remove_authorized_trader(&trader_program)?;
-    
+    config.decrement_trader_count();
+
     // Emit event
     emit!(AuthorizedTraderRemoved {
         trader_program,
+        total_traders: config.trader_count,
         admin: ctx.accounts.admin.key(),
         timestamp: Clock::get()?.unix_timestamp,
     });
-    
+
     msg!(
-        "Authorized trader removed: program={}, admin={}",
+        "Authorized trader removed: program={}, admin={}, total={}",
         trader_program,
-        ctx.accounts.admin.key()
+        ctx.accounts.admin.key(),
+        config.trader_count
     );
-    
+
+    Ok(())
+}
+
+pub fn set_outflow_limit_handler(
+    ctx: Context<SetTraderOutflowLimit>,
+    token_mint: Pubkey,
+    max_per_transfer: u64,
+    window_limit: u64,
+    window_duration: i64,
+) -> Result<()> {
+    let trader_program = ctx.accounts.authorized_trader.trader_program;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let outflow_limit = &mut ctx.accounts.outflow_limit;
+
+    // Initialize limit if new
+    if outflow_limit.trader_program == Pubkey::default() {
+        outflow_limit.initialize(trader_program, token_mint, ctx.bumps.outflow_limit);
+    }
+
+    outflow_limit.configure(max_per_transfer, window_limit, window_duration, timestamp)?;
+
+    // Emit event
+    emit!(TraderOutflowLimitConfigured {
+        trader_program,
+        token_mint,
+        max_per_transfer,
+        window_limit,
+        window_duration,
+        admin: ctx.accounts.admin.key(),
+        timestamp,
+    });
+
+    msg!(
+        "Trader outflow limit configured: program={}, token={}, max_per_transfer={}, window_limit={}, window_duration={}",
+        trader_program,
+        token_mint,
+        max_per_transfer,
+        window_limit,
+        window_duration
+    );
+
+    Ok(())
+}
+
+pub fn remove_outflow_limit_handler(ctx: Context<RemoveTraderOutflowLimit>) -> Result<()> {
+    let outflow_limit = &ctx.accounts.outflow_limit;
+
+    // Emit event
+    emit!(TraderOutflowLimitRemoved {
+        trader_program: outflow_limit.trader_program,
+        token_mint: outflow_limit.token_mint,
+        total_outflow: outflow_limit.total_outflow,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Trader outflow limit removed: program={}, token={}",
+        outflow_limit.trader_program,
+        outflow_limit.token_mint
+    );
+
     Ok(())
 }
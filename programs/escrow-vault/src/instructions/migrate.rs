@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::VaultError;
+use crate::events::*;
+use crate::migration::{realloc_and_load, store_account};
+
+/// Permissionless: upgrade a UserBalance created with an older layout
+/// (before the free/locked split or before versioning) to the current layout
+/// Existing `balance` is kept as free balance, `locked` starts at 0
+/// (collateral committed before the upgrade was already removed from `balance`;
+/// the trading program credits it back for pre-upgrade trades instead of unlocking)
+#[derive(Accounts)]
+pub struct MigrateUserBalance<'info> {
+    /// CHECK: Old layouts cannot be loaded as Account<UserBalance> - owner,
+    /// discriminator and PDA seeds are validated in handler
+    #[account(mut)]
+    pub user_balance: UncheckedAccount<'info>,
+
+    /// Pays the rent for the additional bytes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: upgrade a VaultAuthority created with an older layout
+#[derive(Accounts)]
+pub struct MigrateVaultAuthority<'info> {
+    /// CHECK: Old layouts cannot be loaded as Account<VaultAuthority> - owner,
+    /// discriminator and PDA seeds are validated in handler
+    #[account(mut)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Pays the rent for the additional bytes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade VaultConfig created with an older layout (Admin only)
+#[derive(Accounts)]
+pub struct MigrateVaultConfig<'info> {
+    /// CHECK: Old layouts cannot be loaded as Account<VaultConfig> - owner,
+    /// discriminator, seeds and admin are validated in handler
+    #[account(
+        mut,
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// Admin signer (must match config.admin) - pays the rent for the additional bytes
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_user_balance_handler(ctx: Context<MigrateUserBalance>) -> Result<()> {
+    let user_balance_info = ctx.accounts.user_balance.to_account_info();
+
+    // Grow account and load with the current layout (appended fields read as 0)
+    let mut user_balance: UserBalance = realloc_and_load(
+        &user_balance_info,
+        &crate::ID,
+        8 + UserBalance::INIT_SPACE,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Validate PDA derivation with stored bump
+    let expected = Pubkey::create_program_address(
+        &[
+            UserBalance::USER_BALANCE_SEED,
+            user_balance.user.as_ref(),
+            user_balance.token_mint.as_ref(),
+            &[user_balance.bump],
+        ],
+        &crate::ID,
+    ).map_err(|_| VaultError::InvalidAccountOwner)?;
+    require!(
+        expected == user_balance_info.key(),
+        VaultError::InvalidAccountOwner
+    );
+
+    let previous_version = user_balance.version;
+    user_balance.upgrade_version()?;
+    store_account(&user_balance_info, &user_balance)?;
+
+    emit!(UserBalanceMigrated {
+        user: user_balance.user,
+        token_mint: user_balance.token_mint,
+        free_balance: user_balance.balance,
+        locked_balance: user_balance.locked,
+        previous_version,
+        version: user_balance.version,
+        payer: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "UserBalance migrated: user={}, token={}, free={}, locked={}, version={} -> {}",
+        user_balance.user,
+        user_balance.token_mint,
+        user_balance.balance,
+        user_balance.locked,
+        previous_version,
+        user_balance.version
+    );
+
+    Ok(())
+}
+
+pub fn migrate_vault_authority_handler(ctx: Context<MigrateVaultAuthority>) -> Result<()> {
+    let vault_authority_info = ctx.accounts.vault_authority.to_account_info();
+
+    let mut vault_authority: VaultAuthority = realloc_and_load(
+        &vault_authority_info,
+        &crate::ID,
+        8 + VaultAuthority::INIT_SPACE,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Validate PDA derivation with stored bump
+    let expected = Pubkey::create_program_address(
+        &[
+            VaultAuthority::VAULT_AUTHORITY_SEED,
+            vault_authority.token_mint.as_ref(),
+            &[vault_authority.bump],
+        ],
+        &crate::ID,
+    ).map_err(|_| VaultError::InvalidVaultAuthority)?;
+    require!(
+        expected == vault_authority_info.key(),
+        VaultError::InvalidVaultAuthority
+    );
+
+    let previous_version = vault_authority.version;
+    vault_authority.upgrade_version()?;
+    store_account(&vault_authority_info, &vault_authority)?;
+
+    emit!(AccountMigrated {
+        account: vault_authority_info.key(),
+        account_type: "VaultAuthority".to_string(),
+        previous_version,
+        version: vault_authority.version,
+        payer: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "VaultAuthority migrated: token={}, version={} -> {}",
+        vault_authority.token_mint,
+        previous_version,
+        vault_authority.version
+    );
+
+    Ok(())
+}
+
+pub fn migrate_vault_config_handler(ctx: Context<MigrateVaultConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+
+    let mut config: VaultConfig = realloc_and_load(
+        &config_info,
+        &crate::ID,
+        8 + VaultConfig::INIT_SPACE,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Validate admin authority
+    require!(
+        config.admin == ctx.accounts.admin.key(),
+        VaultError::InvalidAdmin
+    );
+
+    let previous_version = config.version;
+    config.upgrade_version()?;
+    store_account(&config_info, &config)?;
+
+    emit!(AccountMigrated {
+        account: config_info.key(),
+        account_type: "VaultConfig".to_string(),
+        previous_version,
+        version: config.version,
+        payer: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "VaultConfig migrated: version={} -> {}",
+        previous_version,
+        config.version
+    );
+
+    Ok(())
+}
@@ -8,6 +8,14 @@ pub mod transfer_out;
 pub mod transfer_balance;
 pub mod manage_trader;
 pub mod emergency;
+pub mod fund_reward_pool;
+pub mod pay_reward;
+pub mod transfer_admin;
+pub mod configure_collateral_mint;
+pub mod reconcile;
+pub mod lock_balance;
+pub mod unlock_balance;
+pub mod migrate;
 
 // Re-export all with glob imports (keeping original structure)
 pub use initialize::*;
@@ -18,4 +26,12 @@ pub use credit_balance::*;
 pub use transfer_out::*;
 pub use transfer_balance::*;
 pub use manage_trader::*;
-pub use emergency::*; 
\ No newline at end of file
+pub use emergency::*;
+pub use fund_reward_pool::*;
+pub use pay_reward::*;
+pub use transfer_admin::*;
+pub use configure_collateral_mint::*;
+pub use reconcile::*;
+pub use lock_balance::*;
+pub use unlock_balance::*;
+pub use migrate::*; 
\ No newline at end of file
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::error::VaultError;
+use crate::events::*;
+
+/// CPI ONLY: Pay a settlement reward out of the reward pool
+/// Used by trading program so rewards are backed by admin funding, not user deposits
+/// 
+/// 🛡️ SIGNER PDA PATTERN IMPLEMENTATION
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey, amount: u64)]
+pub struct PayReward<'info> {
+    #[account(
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+        // ✅ ONLY basic validations in constraints - no CPI authorization here
+    )]
+    pub config: Box<Account<'info, VaultConfig>>,
+    
+    /// AuthorizedTrader PDA of the calling program - matched against the detected caller
+    #[account(
+        seeds = [
+            AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            authorized_trader.trader_program.as_ref()
+        ],
+        bump = authorized_trader.bump,
+    )]
+    pub authorized_trader: Box<Account<'info, AuthorizedTrader>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            RewardPool::REWARD_POOL_SEED,
+            reward_pool.token_mint.as_ref()
+        ],
+        bump = reward_pool.bump,
+        constraint = reward_pool.balance >= amount @ VaultError::RewardPoolUnderfunded,
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VaultAuthority::VAULT_AUTHORITY_SEED,
+            reward_pool.token_mint.as_ref()
+        ],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Box<Account<'info, VaultAuthority>>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault_authority.vault_ata @ VaultError::InvalidTokenAccount,
+        constraint = vault_token_account.mint == reward_pool.token_mint @ VaultError::TokenMintMismatch,
+        constraint = vault_token_account.owner == vault_authority.key() @ VaultError::InvalidVaultAuthority,
+    )]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Reward token mint (required by transfer_checked)
+    #[account(
+        address = reward_pool.token_mint @ VaultError::TokenMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: Recipient token account - validated by token program transfer
+    #[account(mut)]
+    pub recipient_token_account: AccountInfo<'info>,
+    
+    /// Outflow limit of the calling program for this mint
+    /// CHECK: Address fixed by seeds - no account at the PDA means no limit configured
+    #[account(
+        mut,
+        seeds = [
+            TraderOutflowLimit::TRADER_OUTFLOW_LIMIT_SEED,
+            authorized_trader.trader_program.as_ref(),
+            reward_pool.token_mint.as_ref()
+        ],
+        bump,
+    )]
+    pub outflow_limit: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// 🛡️ CALLER SIGNER - ["vault_signer"] PDA of the calling program (invoke_signed)
+    /// Validated against authorized_trader.signer in handler
+    pub caller_signer: Signer<'info>,
+}
+
+/// 🛡️ SIGNER PDA PATTERN - CPI caller authenticated by signature
+pub fn handler(ctx: Context<PayReward>, recipient: Pubkey, amount: u64) -> Result<()> {
+    // 🔍 STEP 1: Caller proves its identity by signing with its vault_signer PDA
+    // 🔒 STEP 2: Validate signer against AuthorizedTrader and operation grant
+    let caller_program_id = ctx.accounts.config.validate_cpi_caller_precise(
+        &ctx.accounts.caller_signer,
+        &ctx.accounts.authorized_trader,
+        AuthorizedTrader::OP_PAY_REWARD,
+    )?;
+    
+    // 🔒 STEP 3: Validate business logic parameters
+    require!(amount > 0, VaultError::ZeroAmount);
+    
+    // 🔒 Per-mint outflow limit of the calling program (if configured)
+    TraderOutflowLimit::enforce(
+        &ctx.accounts.outflow_limit.to_account_info(),
+        amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+    
+    // ✅ STEP 4: Debit pool first - refuses when underfunded
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.pay(amount)?;
+    ctx.accounts.vault_authority.subtract_reward_liability(amount)?;
+    
+    // Rewards must never be paid out of user deposits
+    let reserves_after = ctx.accounts.vault_token_account.amount
+        .checked_sub(amount)
+        .ok_or(VaultError::ReservesInsufficient)?;
+    ctx.accounts.vault_authority.ensure_solvent(reserves_after)?;
+    
+    // ✅ STEP 5: Execute token transfer
+    let seeds = &[
+        VaultAuthority::VAULT_AUTHORITY_SEED,
+        reward_pool.token_mint.as_ref(),
+        &[ctx.accounts.vault_authority.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.recipient_token_account.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+    
+    // 📡 STEP 6: Emit event with remaining pool balance
+    emit!(RewardPaid {
+        token_mint: reward_pool.token_mint,
+        recipient,
+        amount,
+        pool_balance: reward_pool.balance,
+        caller_program: caller_program_id,
+    });
+    
+    msg!(
+        "✅ Reward paid from pool: token={}, recipient={}, amount={}, pool_balance={}, precise_caller={}",
+        reward_pool.token_mint,
+        recipient,
+        amount,
+        reward_pool.balance,
+        caller_program_id
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::error::VaultError;
+use crate::events::*;
+
+/// Permissionless proof-of-reserves: compare liabilities with vault_ata balance
+#[derive(Accounts)]
+#[instruction(token_mint: Pubkey)]
+pub struct Reconcile<'info> {
+    #[account(
+        seeds = [
+            VaultAuthority::VAULT_AUTHORITY_SEED,
+            token_mint.as_ref()
+        ],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Box<Account<'info, VaultAuthority>>,
+    
+    #[account(
+        constraint = vault_ata.key() == vault_authority.vault_ata @ VaultError::InvalidTokenAccount,
+    )]
+    pub vault_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+pub fn handler(ctx: Context<Reconcile>, token_mint: Pubkey) -> Result<()> {
+    let reserves = ctx.accounts.vault_ata.amount;
+    let vault_authority = &ctx.accounts.vault_authority;
+    let total_deposits = vault_authority.total_deposits;
+    
+    // Reward pool balance is also held by vault_ata (tracked on VaultAuthority)
+    let reward_pool_balance = vault_authority.reward_pool_balance;
+    let liabilities = vault_authority.liabilities()?;
+    let surplus = reserves.saturating_sub(liabilities);
+    let deficit = liabilities.saturating_sub(reserves);
+    
+    emit!(ReserveSnapshot {
+        token_mint,
+        vault_ata: ctx.accounts.vault_ata.key(),
+        reserves,
+        total_deposits,
+        reward_pool_balance,
+        surplus,
+        deficit,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!(
+        "Reserve snapshot: token={}, reserves={}, total_deposits={}, reward_pool={}, surplus={}, deficit={}",
+        token_mint,
+        reserves,
+        total_deposits,
+        reward_pool_balance,
+        surplus,
+        deficit
+    );
+    
+    Ok(())
+}
@@ -2,15 +2,14 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::VaultError;
 use crate::events::*;
-use crate::utils::get_cpi_caller_program_id;
 
 /// CPI ONLY: Subtract user balance (exact EVM slashBalance mapping)
 /// Used by trading program to "lock" collateral
 /// 
-/// 🛡️ INSTRUCTION SYSVAR PATTERN IMPLEMENTATION
-/// - Uses instruction sysvar to detect exact caller program ID
-/// - Most accurate method for CPI caller validation
-/// - Used by high-security protocols like Jupiter, Orca
+/// 🛡️ SIGNER PDA PATTERN IMPLEMENTATION
+/// - Caller program signs with its ["vault_signer"] PDA via invoke_signed
+/// - Direct transactions and other programs cannot produce that signature
+/// - Signer must match the AuthorizedTrader PDA of the caller program
 #[derive(Accounts)]
 #[instruction(amount: u64)]
 pub struct SlashBalance<'info> {
@@ -21,6 +20,16 @@ pub struct SlashBalance<'info> {
     )]
     pub config: Account<'info, VaultConfig>,
     
+    /// AuthorizedTrader PDA of the calling program - matched against the detected caller
+    #[account(
+        seeds = [
+            AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            authorized_trader.trader_program.as_ref()
+        ],
+        bump = authorized_trader.bump,
+    )]
+    pub authorized_trader: Account<'info, AuthorizedTrader>,
+    
     #[account(
         mut,
         seeds = [
@@ -43,23 +52,19 @@ pub struct SlashBalance<'info> {
     )]
     pub vault_authority: Account<'info, VaultAuthority>,
     
-    /// 🛡️ INSTRUCTION SYSVAR - For precise caller detection
-    /// CHECK: Validated by constraint to ensure it's the instruction sysvar
-    #[account(
-        constraint = instruction_sysvar.key() == solana_program::sysvar::instructions::ID @ VaultError::InvalidInstructionSysvar
-    )]
-    pub instruction_sysvar: AccountInfo<'info>,
+    /// 🛡️ CALLER SIGNER - ["vault_signer"] PDA of the calling program (invoke_signed)
+    /// Validated against authorized_trader.signer in handler
+    pub caller_signer: Signer<'info>,
 }
 
-/// 🛡️ INSTRUCTION SYSVAR PATTERN - Most accurate CPI caller detection
+/// 🛡️ SIGNER PDA PATTERN - CPI caller authenticated by signature
 pub fn handler(ctx: Context<SlashBalance>, amount: u64) -> Result<()> {
-    // 🔍 STEP 1: Get precise caller program ID from instruction sysvar
-    let caller_program_id = get_cpi_caller_program_id(&ctx.accounts.instruction_sysvar)?;
-    
-    // 🔒 STEP 2: Validate CPI caller authorization using precise detection
-    ctx.accounts.config.validate_cpi_caller_precise(
-        &caller_program_id, 
-        "SlashBalance"
+    // 🔍 STEP 1: Caller proves its identity by signing with its vault_signer PDA
+    // 🔒 STEP 2: Validate signer against AuthorizedTrader and operation grant
+    let caller_program_id = ctx.accounts.config.validate_cpi_caller_precise(
+        &ctx.accounts.caller_signer,
+        &ctx.accounts.authorized_trader,
+        AuthorizedTrader::OP_SLASH,
     )?;
     
     // 🔒 STEP 3: Validate business logic parameters
@@ -80,6 +85,8 @@ pub fn handler(ctx: Context<SlashBalance>, amount: u64) -> Result<()> {
         user: user_balance.user,
         token_mint: user_balance.token_mint,
         amount,
+        free_balance: user_balance.balance,
+        locked_balance: user_balance.locked,
         caller_program: caller_program_id,
     });
     
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::VaultError;
+use crate::events::*;
+
+/// Two-step admin / emergency admin transfer: admin proposes / cancels (Admin only)
+#[derive(Accounts)]
+pub struct ManageAdminTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ VaultError::InvalidAdmin,
+    )]
+    pub config: Account<'info, VaultConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Two-step admin transfer: pending admin accepts
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, VaultConfig>,
+    
+    pub new_admin: Signer<'info>,
+}
+
+/// Emergency admin rotation: pending emergency admin accepts
+#[derive(Accounts)]
+pub struct AcceptEmergencyAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, VaultConfig>,
+    
+    pub new_emergency_admin: Signer<'info>,
+}
+
+pub fn propose_admin_handler(
+    ctx: Context<ManageAdminTransfer>,
+    new_admin: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    // Record pending admin
+    config.propose_admin(new_admin)?;
+    
+    // Emit event
+    emit!(AdminTransferProposed {
+        admin: config.admin,
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Admin transfer proposed: admin={}, pending_admin={}", config.admin, new_admin);
+    Ok(())
+}
+
+pub fn accept_admin_handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let new_admin = ctx.accounts.new_admin.key();
+    
+    // Promote pending admin
+    let previous_admin = config.accept_admin(new_admin)?;
+    
+    // Emit event
+    emit!(AdminTransferAccepted {
+        previous_admin,
+        new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Admin transfer accepted: previous_admin={}, new_admin={}", previous_admin, new_admin);
+    Ok(())
+}
+
+pub fn cancel_admin_transfer_handler(ctx: Context<ManageAdminTransfer>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    // Clear pending admin
+    let cancelled_admin = config.cancel_admin_transfer()?;
+    
+    // Emit event
+    emit!(AdminTransferCancelled {
+        admin: config.admin,
+        cancelled_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Admin transfer cancelled: admin={}, cancelled_admin={}", config.admin, cancelled_admin);
+    Ok(())
+}
+
+pub fn propose_emergency_admin_handler(
+    ctx: Context<ManageAdminTransfer>,
+    new_emergency_admin: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    // Record pending emergency admin
+    config.propose_emergency_admin(new_emergency_admin)?;
+    
+    // Emit event
+    emit!(EmergencyAdminTransferProposed {
+        admin: config.admin,
+        emergency_admin: config.emergency_admin,
+        pending_emergency_admin: new_emergency_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!(
+        "Emergency admin rotation proposed: emergency_admin={}, pending_emergency_admin={}",
+        config.emergency_admin,
+        new_emergency_admin
+    );
+    Ok(())
+}
+
+pub fn accept_emergency_admin_handler(ctx: Context<AcceptEmergencyAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let new_emergency_admin = ctx.accounts.new_emergency_admin.key();
+    
+    // Promote pending emergency admin
+    let previous_emergency_admin = config.accept_emergency_admin(new_emergency_admin)?;
+    
+    // Emit event
+    emit!(EmergencyAdminTransferAccepted {
+        previous_emergency_admin,
+        new_emergency_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!(
+        "Emergency admin rotation accepted: previous_emergency_admin={}, new_emergency_admin={}",
+        previous_emergency_admin,
+        new_emergency_admin
+    );
+    Ok(())
+}
+
+pub fn cancel_emergency_admin_transfer_handler(ctx: Context<ManageAdminTransfer>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    // Clear pending emergency admin
+    let cancelled_emergency_admin = config.cancel_emergency_admin_transfer()?;
+    
+    // Emit event
+    emit!(EmergencyAdminTransferCancelled {
+        admin: config.admin,
+        cancelled_emergency_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!(
+        "Emergency admin rotation cancelled: admin={}, cancelled_emergency_admin={}",
+        config.admin,
+        cancelled_emergency_admin
+    );
+    Ok(())
+}
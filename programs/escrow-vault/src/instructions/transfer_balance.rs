@@ -2,12 +2,11 @@ use crate::error::VaultError;
 use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
-use crate::utils::get_cpi_caller_program_id;
 
 /// CPI ONLY: Transfer between user balances (exact EVM transferBalance mapping)
 /// Used by trading program for internal transfers
 ///
-/// 🛡️ INSTRUCTION SYSVAR PATTERN IMPLEMENTATION
+/// 🛡️ SIGNER PDA PATTERN IMPLEMENTATION
 #[derive(Accounts)]
 #[instruction(from_user: Pubkey, to_user: Pubkey, amount: u64)]
 pub struct TransferBalance<'info> {
@@ -17,6 +16,16 @@ pub struct TransferBalance<'info> {
         // ✅ ONLY basic validations in constraints - no CPI authorization here
     )]
     pub config: Account<'info, VaultConfig>,
+    
+    /// AuthorizedTrader PDA of the calling program - matched against the detected caller
+    #[account(
+        seeds = [
+            AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            authorized_trader.trader_program.as_ref()
+        ],
+        bump = authorized_trader.bump,
+    )]
+    pub authorized_trader: Account<'info, AuthorizedTrader>,
 
     #[account(
         mut,
@@ -51,28 +60,25 @@ pub struct TransferBalance<'info> {
     )]
     pub vault_authority: Account<'info, VaultAuthority>,
 
-    /// 🛡️ INSTRUCTION SYSVAR - For precise caller detection
-    /// CHECK: Validated by constraint to ensure it's the instruction sysvar
-    #[account(
-        constraint = instruction_sysvar.key() == solana_program::sysvar::instructions::ID @ VaultError::InvalidInstructionSysvar
-    )]
-    pub instruction_sysvar: AccountInfo<'info>,
+    /// 🛡️ CALLER SIGNER - ["vault_signer"] PDA of the calling program (invoke_signed)
+    /// Validated against authorized_trader.signer in handler
+    pub caller_signer: Signer<'info>,
 }
 
-/// 🛡️ INSTRUCTION SYSVAR PATTERN - Most accurate CPI caller detection
+/// 🛡️ SIGNER PDA PATTERN - CPI caller authenticated by signature
 pub fn handler(
     ctx: Context<TransferBalance>,
     from_user: Pubkey,
     to_user: Pubkey,
     amount: u64,
 ) -> Result<()> {
-    // 🔍 STEP 1: Get precise caller program ID from instruction sysvar
-    let caller_program_id = get_cpi_caller_program_id(&ctx.accounts.instruction_sysvar)?;
-
-    // 🔒 STEP 2: Validate CPI caller authorization using precise detection
-    ctx.accounts
-        .config
-        .validate_cpi_caller_precise(&caller_program_id, "TransferBalance")?;
+    // 🔍 STEP 1: Caller proves its identity by signing with its vault_signer PDA
+    // 🔒 STEP 2: Validate signer against AuthorizedTrader and operation grant
+    let caller_program_id = ctx.accounts.config.validate_cpi_caller_precise(
+        &ctx.accounts.caller_signer,
+        &ctx.accounts.authorized_trader,
+        AuthorizedTrader::OP_TRANSFER_BALANCE,
+    )?;
 
     // 🔒 STEP 3: Validate business logic parameters
     require!(amount > 0, VaultError::ZeroAmount);
@@ -102,6 +108,10 @@ pub fn handler(
         to_user,
         token_mint: from_balance.token_mint,
         amount,
+        from_free_balance: from_balance.balance,
+        from_locked_balance: from_balance.locked,
+        to_free_balance: to_balance.balance,
+        to_locked_balance: to_balance.locked,
         caller_program: caller_program_id,
     });
 
@@ -1,14 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::error::VaultError;
 use crate::events::*;
-use crate::utils::get_cpi_caller_program_id;
 
 /// CPI ONLY: Transfer tokens out of vault (exact EVM transferOut mapping)
 /// Used by trading program for settlement and cancellation
 /// 
-/// 🛡️ INSTRUCTION SYSVAR PATTERN IMPLEMENTATION
+/// 🛡️ SIGNER PDA PATTERN IMPLEMENTATION
 #[derive(Accounts)]
 #[instruction(recipient: Pubkey, amount: u64)]
 pub struct TransferOut<'info> {
@@ -19,6 +18,16 @@ pub struct TransferOut<'info> {
     )]
     pub config: Box<Account<'info, VaultConfig>>,
     
+    /// AuthorizedTrader PDA of the calling program - matched against the detected caller
+    #[account(
+        seeds = [
+            AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            authorized_trader.trader_program.as_ref()
+        ],
+        bump = authorized_trader.bump,
+    )]
+    pub authorized_trader: Box<Account<'info, AuthorizedTrader>>,
+    
     #[account(
         mut,
         seeds = [
@@ -43,40 +52,72 @@ pub struct TransferOut<'info> {
     
     #[account(
         mut,
+        constraint = vault_token_account.key() == vault_authority.vault_ata @ VaultError::InvalidTokenAccount,
         constraint = vault_token_account.mint == user_balance.token_mint @ VaultError::TokenMintMismatch,
         constraint = vault_token_account.owner == vault_authority.key() @ VaultError::InvalidVaultAuthority,
     )]
-    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Token mint being transferred (required by transfer_checked)
+    #[account(
+        address = user_balance.token_mint @ VaultError::TokenMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     
     /// CHECK: Recipient token account - validated in handler
     #[account(mut)]
     pub recipient_token_account: AccountInfo<'info>,
     
-    pub token_program: Program<'info, Token>,
-    
-    /// 🛡️ INSTRUCTION SYSVAR - For precise caller detection
-    /// CHECK: Validated by constraint to ensure it's the instruction sysvar
+    /// Outflow limit of the calling program for this mint
+    /// CHECK: Address fixed by seeds - no account at the PDA means no limit configured
     #[account(
-        constraint = instruction_sysvar.key() == solana_program::sysvar::instructions::ID @ VaultError::InvalidInstructionSysvar
+        mut,
+        seeds = [
+            TraderOutflowLimit::TRADER_OUTFLOW_LIMIT_SEED,
+            authorized_trader.trader_program.as_ref(),
+            user_balance.token_mint.as_ref()
+        ],
+        bump,
     )]
-    pub instruction_sysvar: AccountInfo<'info>,
+    pub outflow_limit: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// 🛡️ CALLER SIGNER - ["vault_signer"] PDA of the calling program (invoke_signed)
+    /// Validated against authorized_trader.signer in handler
+    pub caller_signer: Signer<'info>,
 }
 
-/// 🛡️ INSTRUCTION SYSVAR PATTERN - Most accurate CPI caller detection
+/// 🛡️ SIGNER PDA PATTERN - CPI caller authenticated by signature
 pub fn handler(ctx: Context<TransferOut>, recipient: Pubkey, amount: u64) -> Result<()> {
-    // 🔍 STEP 1: Get precise caller program ID from instruction sysvar
-    let caller_program_id = get_cpi_caller_program_id(&ctx.accounts.instruction_sysvar)?;
-    
-    // 🔒 STEP 2: Validate CPI caller authorization using precise detection
-    ctx.accounts.config.validate_cpi_caller_precise(
-        &caller_program_id, 
-        "TransferOut"
+    // 🔍 STEP 1: Caller proves its identity by signing with its vault_signer PDA
+    // 🔒 STEP 2: Validate signer against AuthorizedTrader and operation grant
+    let caller_program_id = ctx.accounts.config.validate_cpi_caller_precise(
+        &ctx.accounts.caller_signer,
+        &ctx.accounts.authorized_trader,
+        AuthorizedTrader::OP_TRANSFER_OUT,
     )?;
     
     // 🔒 STEP 3: Validate business logic parameters
     require!(amount > 0, VaultError::ZeroAmount);
     
-    // ✅ STEP 4: Execute token transfer
+    // 🔒 Per-mint outflow limit of the calling program (if configured)
+    TraderOutflowLimit::enforce(
+        &ctx.accounts.outflow_limit.to_account_info(),
+        amount,
+        Clock::get()?.unix_timestamp,
+    )?;
+    
+    // 🔒 STEP 4: Solvency guard - tokens leaving the vault also leave total deposits
+    let vault_authority = &mut ctx.accounts.vault_authority;
+    vault_authority.subtract_deposit(amount)?;
+    let reserves_after = ctx.accounts.vault_token_account.amount
+        .checked_sub(amount)
+        .ok_or(VaultError::ReservesInsufficient)?;
+    vault_authority.ensure_solvent(reserves_after)?;
+    
+    // ✅ STEP 5: Execute token transfer
     let user_balance = &mut ctx.accounts.user_balance;
     
     // Create PDA signer seeds
@@ -88,8 +129,9 @@ pub fn handler(ctx: Context<TransferOut>, recipient: Pubkey, amount: u64) -> Res
     let signer = &[&seeds[..]];
     
     // Perform CPI to token program
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.vault_token_account.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
         to: ctx.accounts.recipient_token_account.to_account_info(),
         authority: ctx.accounts.vault_authority.to_account_info(),
     };
@@ -97,23 +139,25 @@ pub fn handler(ctx: Context<TransferOut>, recipient: Pubkey, amount: u64) -> Res
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
     
-    token::transfer(cpi_ctx, amount)?;
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
     
     // Update user balance
     user_balance.balance = user_balance.balance
         .checked_sub(amount)
         .ok_or(VaultError::ArithmeticOverflow)?;
     
-    // 📡 STEP 5: Emit event with precise caller info
+    // 📡 STEP 6: Emit event with precise caller info
     emit!(TokensTransferredOut {
         user: user_balance.user,
         token_mint: user_balance.token_mint,
         recipient,
         amount,
+        free_balance: user_balance.balance,
+        locked_balance: user_balance.locked,
         caller_program: caller_program_id,
     });
     
-    // 📝 STEP 6: Structured logging with precise caller
+    // 📝 STEP 7: Structured logging with precise caller
     msg!(
         "✅ Tokens transferred out successfully: user={}, token={}, recipient={}, amount={}, remaining_balance={}, precise_caller={}",
         user_balance.user,
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::VaultError;
+use crate::events::*;
+
+/// CPI ONLY: Move locked balance back to free balance
+/// Used by trading program to release collateral of a settled or cancelled trade
+/// 
+/// 🛡️ SIGNER PDA PATTERN IMPLEMENTATION
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct UnlockBalance<'info> {
+    #[account(
+        seeds = [VaultConfig::VAULT_CONFIG_SEED],
+        bump = config.bump,
+        // ✅ ONLY basic validations in constraints - no CPI authorization here
+    )]
+    pub config: Account<'info, VaultConfig>,
+    
+    /// AuthorizedTrader PDA of the calling program - matched against the detected caller
+    #[account(
+        seeds = [
+            AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            authorized_trader.trader_program.as_ref()
+        ],
+        bump = authorized_trader.bump,
+    )]
+    pub authorized_trader: Account<'info, AuthorizedTrader>,
+    
+    #[account(
+        mut,
+        seeds = [
+            UserBalance::USER_BALANCE_SEED,
+            user_balance.user.as_ref(),
+            user_balance.token_mint.as_ref()
+        ],
+        bump = user_balance.bump,
+        constraint = user_balance.locked >= amount @ VaultError::InsufficientLockedBalance,
+    )]
+    pub user_balance: Account<'info, UserBalance>,
+    
+    #[account(
+        seeds = [
+            VaultAuthority::VAULT_AUTHORITY_SEED,
+            user_balance.token_mint.as_ref()
+        ],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+    
+    /// 🛡️ CALLER SIGNER - ["vault_signer"] PDA of the calling program (invoke_signed)
+    /// Validated against authorized_trader.signer in handler
+    pub caller_signer: Signer<'info>,
+}
+
+/// 🛡️ SIGNER PDA PATTERN - CPI caller authenticated by signature
+pub fn handler(ctx: Context<UnlockBalance>, amount: u64) -> Result<()> {
+    // 🔍 STEP 1: Caller proves its identity by signing with its vault_signer PDA
+    // 🔒 STEP 2: Validate signer against AuthorizedTrader and operation grant
+    let caller_program_id = ctx.accounts.config.validate_cpi_caller_precise(
+        &ctx.accounts.caller_signer,
+        &ctx.accounts.authorized_trader,
+        AuthorizedTrader::OP_LOCK,
+    )?;
+    
+    // 🔒 STEP 3: Validate business logic parameters
+    require!(amount > 0, VaultError::ZeroAmount);
+    
+    // ✅ STEP 4: Execute business logic
+    let user_balance = &mut ctx.accounts.user_balance;
+    user_balance.unlock_balance(amount)?;
+    
+    // 📡 STEP 5: Emit event with free/locked balances
+    emit!(BalanceUnlocked {
+        user: user_balance.user,
+        token_mint: user_balance.token_mint,
+        amount,
+        free_balance: user_balance.balance,
+        locked_balance: user_balance.locked,
+        caller_program: caller_program_id,
+    });
+    
+    // 📝 STEP 6: Structured logging with precise caller
+    msg!(
+        "✅ Balance unlocked successfully: user={}, token={}, amount={}, free={}, locked={}, precise_caller={}",
+        user_balance.user,
+        user_balance.token_mint,
+        amount,
+        user_balance.balance,
+        user_balance.locked,
+        caller_program_id
+    );
+    
+    Ok(())
+}
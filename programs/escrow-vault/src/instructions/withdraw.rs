@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::error::VaultError;
 use crate::events::*;
 
-/// User withdraws available balance (ANY TOKEN SUPPORTED)
+/// User withdraws free balance (ANY TOKEN SUPPORTED)
+/// Collateral locked for open trades is not withdrawable
 #[derive(Accounts)]
 #[instruction(amount: u64)]
 pub struct WithdrawCollateral<'info> {
@@ -44,19 +45,26 @@ pub struct WithdrawCollateral<'info> {
         constraint = vault_ata.key() == vault_authority.vault_ata @ VaultError::InvalidTokenMint,
         constraint = vault_ata.mint == user_balance.token_mint @ VaultError::InvalidTokenMint,
     )]
-    pub vault_ata: Box<Account<'info, TokenAccount>>,
+    pub vault_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
     #[account(
         mut,
         constraint = user_ata.mint == user_balance.token_mint @ VaultError::InvalidTokenMint,
         constraint = user_ata.owner == user.key() @ VaultError::InvalidAccountOwner,
     )]
-    pub user_ata: Box<Account<'info, TokenAccount>>,
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Token mint being withdrawn (required by transfer_checked)
+    #[account(
+        address = user_balance.token_mint @ VaultError::InvalidTokenMint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
@@ -66,12 +74,18 @@ pub fn handler(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
     let user_balance = &mut ctx.accounts.user_balance;
     let vault_authority = &mut ctx.accounts.vault_authority;
     
-    // Subtract from user balance (exact EVM logic)
+    // Subtract from free balance only (locked collateral stays committed)
     user_balance.slash_balance(amount)?;
     
     // Subtract from total deposits (exact EVM logic)
     vault_authority.subtract_deposit(amount)?;
     
+    // Solvency guard - refuse if remaining reserves would not cover liabilities
+    let reserves_after = ctx.accounts.vault_ata.amount
+        .checked_sub(amount)
+        .ok_or(VaultError::ReservesInsufficient)?;
+    vault_authority.ensure_solvent(reserves_after)?;
+    
     // Transfer tokens from vault to user
     let token_mint = vault_authority.token_mint;
     let vault_authority_bump = vault_authority.bump;
@@ -86,15 +100,17 @@ pub fn handler(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
     
     let transfer_cpi = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.vault_ata.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
             to: ctx.accounts.user_ata.to_account_info(),
             authority: vault_authority.to_account_info(),
         },
         signer_seeds_slice,
     );
     
-    token::transfer(transfer_cpi, amount)?;
+    // Token-2022 transfer fee (if any) is withheld from the amount the user receives
+    token_interface::transfer_checked(transfer_cpi, amount, ctx.accounts.token_mint.decimals)?;
     
     // Emit withdrawal event
     emit!(CollateralWithdrawn {
@@ -102,6 +118,7 @@ pub fn handler(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
         token_mint: user_balance.token_mint,
         amount,
         remaining_balance: user_balance.balance,
+        locked_balance: user_balance.locked,
     });
     
     msg!(
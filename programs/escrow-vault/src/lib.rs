@@ -8,6 +8,7 @@ pub mod state;
 pub mod error;
 pub mod events;
 pub mod utils;
+pub mod migration;
 
 use instructions::*;
 
@@ -24,20 +25,48 @@ pub mod escrow_vault {
         instructions::initialize::handler(ctx, admin, emergency_admin)
     }
 
-    /// Add authorized trading program (Admin only)
+    /// Authorize trading program with allowed CPI operations (Admin only)
     pub fn add_authorized_trader(
-        ctx: Context<ManageAuthorizedTrader>,
+        ctx: Context<AddAuthorizedTrader>,
         trader_program: Pubkey,
+        operations: u8,
     ) -> Result<()> {
-        instructions::manage_trader::add_handler(ctx, trader_program)
+        instructions::manage_trader::add_handler(ctx, trader_program, operations)
     }
 
-    /// Remove authorized trading program (Admin only)
-    pub fn remove_authorized_trader(
-        ctx: Context<ManageAuthorizedTrader>,
-        trader_program: Pubkey,
+    /// Change allowed CPI operations of a trading program (Admin only)
+    pub fn update_authorized_trader(
+        ctx: Context<UpdateAuthorizedTrader>,
+        operations: u8,
     ) -> Result<()> {
-        instructions::manage_trader::remove_handler(ctx, trader_program)
+        instructions::manage_trader::update_handler(ctx, operations)
+    }
+
+    /// Revoke trading program (Admin only)
+    pub fn remove_authorized_trader(ctx: Context<RemoveAuthorizedTrader>) -> Result<()> {
+        instructions::manage_trader::remove_handler(ctx)
+    }
+
+    /// Add / update per-mint outflow limit of a trading program (Admin only)
+    pub fn set_trader_outflow_limit(
+        ctx: Context<SetTraderOutflowLimit>,
+        token_mint: Pubkey,
+        max_per_transfer: u64,
+        window_limit: u64,
+        window_duration: i64,
+    ) -> Result<()> {
+        instructions::manage_trader::set_outflow_limit_handler(
+            ctx,
+            token_mint,
+            max_per_transfer,
+            window_limit,
+            window_duration,
+        )
+    }
+
+    /// Remove per-mint outflow limit of a trading program (Admin only)
+    pub fn remove_trader_outflow_limit(ctx: Context<RemoveTraderOutflowLimit>) -> Result<()> {
+        instructions::manage_trader::remove_outflow_limit_handler(ctx)
     }
 
     /// Emergency pause (Emergency admin only)
@@ -50,7 +79,53 @@ pub mod escrow_vault {
         instructions::emergency::unpause_handler(ctx)
     }
 
-    /// User deposits collateral tokens (ANY TOKEN SUPPORTED)
+    /// Propose new admin - step 1 of two-step transfer (Admin only)
+    pub fn propose_admin(
+        ctx: Context<ManageAdminTransfer>,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        instructions::transfer_admin::propose_admin_handler(ctx, new_admin)
+    }
+
+    /// Accept admin role - step 2 of two-step transfer (Pending admin only)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::transfer_admin::accept_admin_handler(ctx)
+    }
+
+    /// Cancel pending admin transfer (Admin only)
+    pub fn cancel_admin_transfer(ctx: Context<ManageAdminTransfer>) -> Result<()> {
+        instructions::transfer_admin::cancel_admin_transfer_handler(ctx)
+    }
+
+    /// Propose new emergency admin - step 1 of rotation (Admin only)
+    pub fn propose_emergency_admin(
+        ctx: Context<ManageAdminTransfer>,
+        new_emergency_admin: Pubkey,
+    ) -> Result<()> {
+        instructions::transfer_admin::propose_emergency_admin_handler(ctx, new_emergency_admin)
+    }
+
+    /// Accept emergency admin role - step 2 of rotation (Pending emergency admin only)
+    pub fn accept_emergency_admin(ctx: Context<AcceptEmergencyAdmin>) -> Result<()> {
+        instructions::transfer_admin::accept_emergency_admin_handler(ctx)
+    }
+
+    /// Cancel pending emergency admin rotation (Admin only)
+    pub fn cancel_emergency_admin_transfer(ctx: Context<ManageAdminTransfer>) -> Result<()> {
+        instructions::transfer_admin::cancel_emergency_admin_transfer_handler(ctx)
+    }
+
+    /// Add / update collateral mint allowlist entry (Admin only)
+    pub fn configure_collateral_mint(
+        ctx: Context<ConfigureCollateralMint>,
+        enabled: bool,
+        deposit_cap: u64,
+        min_deposit: u64,
+    ) -> Result<()> {
+        instructions::configure_collateral_mint::handler(ctx, enabled, deposit_cap, min_deposit)
+    }
+
+    /// User deposits collateral tokens (allowlisted mints only)
     pub fn deposit_collateral(
         ctx: Context<DepositCollateral>,
         amount: u64,
@@ -58,7 +133,7 @@ pub mod escrow_vault {
         instructions::deposit::handler(ctx, amount)
     }
 
-    /// User withdraws available balance
+    /// User withdraws free balance (locked collateral excluded)
     pub fn withdraw_collateral(
         ctx: Context<WithdrawCollateral>,
         amount: u64,
@@ -66,6 +141,37 @@ pub mod escrow_vault {
         instructions::withdraw::handler(ctx, amount)
     }
 
+    /// Permissionless: upgrade a UserBalance to the current layout (realloc)
+    pub fn migrate_user_balance(ctx: Context<MigrateUserBalance>) -> Result<()> {
+        instructions::migrate::migrate_user_balance_handler(ctx)
+    }
+
+    /// Permissionless: upgrade a VaultAuthority to the current layout (realloc)
+    pub fn migrate_vault_authority(ctx: Context<MigrateVaultAuthority>) -> Result<()> {
+        instructions::migrate::migrate_vault_authority_handler(ctx)
+    }
+
+    /// Upgrade VaultConfig to the current layout (Admin only, realloc)
+    pub fn migrate_vault_config(ctx: Context<MigrateVaultConfig>) -> Result<()> {
+        instructions::migrate::migrate_vault_config_handler(ctx)
+    }
+
+    /// Fund settlement reward pool for a collateral mint (Admin only)
+    pub fn fund_reward_pool(
+        ctx: Context<FundRewardPool>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::fund_reward_pool::handler(ctx, amount)
+    }
+
+    /// Permissionless: compare total deposits with vault reserves and emit ReserveSnapshot
+    pub fn reconcile(
+        ctx: Context<Reconcile>,
+        token_mint: Pubkey,
+    ) -> Result<()> {
+        instructions::reconcile::handler(ctx, token_mint)
+    }
+
     /// CPI ONLY: Subtract user balance (exact EVM slashBalance mapping)
     /// Used by trading program to "lock" collateral
     pub fn slash_balance(
@@ -84,6 +190,24 @@ pub mod escrow_vault {
         instructions::credit_balance::handler(ctx, amount)
     }
 
+    /// CPI ONLY: Move free balance into locked balance
+    /// Used by trading program to commit collateral to an open trade
+    pub fn lock_balance(
+        ctx: Context<LockBalance>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::lock_balance::handler(ctx, amount)
+    }
+
+    /// CPI ONLY: Move locked balance back to free balance
+    /// Used by trading program to release collateral at settlement / cancellation
+    pub fn unlock_balance(
+        ctx: Context<UnlockBalance>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::unlock_balance::handler(ctx, amount)
+    }
+
     /// CPI ONLY: Transfer tokens out of vault (exact EVM transferOut mapping)
     /// Used by trading program for settlement and cancellation
     pub fn transfer_out(
@@ -104,4 +228,14 @@ pub mod escrow_vault {
     ) -> Result<()> {
         instructions::transfer_balance::handler(ctx, from_user, to_user, amount)
     }
-} 
\ No newline at end of file
+
+    /// CPI ONLY: Pay settlement reward from the reward pool
+    /// Used by trading program so seller rewards are backed by admin funding
+    pub fn pay_reward(
+        ctx: Context<PayReward>,
+        recipient: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::pay_reward::handler(ctx, recipient, amount)
+    }
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+/// Current layout version of versioned state accounts (vault and trading programs)
+/// Accounts created before versioning read `version = 0` after realloc (v1 layout)
+pub const CURRENT_ACCOUNT_VERSION: u8 = 2;
+
+/// Deserialize account bytes with the current layout after zero-extending to `new_space`
+/// Only valid for layouts that grew by appending - a zero-filled tail reads as version 0 / empty reserved
+/// (layouts with fields inserted mid-struct need their own decoder, e.g. trading TradeConfig v0)
+pub fn load_upgraded<T: AccountDeserialize>(data: &[u8], new_space: usize) -> Result<T> {
+    let mut upgraded = data.to_vec();
+    if upgraded.len() < new_space {
+        upgraded.resize(new_space, 0);
+    }
+    T::try_deserialize(&mut &upgraded[..])
+}
+
+/// Grow `account` to `new_space` (zero-filled), top up rent from `payer`
+/// and load it with the current layout
+pub fn realloc_and_load<'info, T: AccountDeserialize>(
+    account: &AccountInfo<'info>,
+    owner: &Pubkey,
+    new_space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<T> {
+    realloc_account(account, owner, new_space, payer, system_program)?;
+
+    let data = account.try_borrow_data()?;
+    load_upgraded(&data, new_space)
+}
+
+/// Grow `account` to `new_space` (zero-filled) and top up rent from `payer`
+/// Existing bytes are kept - used directly when the old layout needs its own decoder
+pub fn realloc_account<'info>(
+    account: &AccountInfo<'info>,
+    owner: &Pubkey,
+    new_space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require_keys_eq!(*account.owner, *owner, ErrorCode::AccountOwnedByWrongProgram);
+
+    if account.data_len() < new_space {
+        account.realloc(new_space, true)?;
+    }
+
+    let required_lamports = Rent::get()?.minimum_balance(account.data_len());
+    let current_lamports = account.lamports();
+    if required_lamports > current_lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            required_lamports - current_lamports,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Write an upgraded account back (discriminator + current layout)
+pub fn store_account<T: AccountSerialize>(account: &AccountInfo, value: &T) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    value.try_serialize(&mut writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{UserBalance, VaultConfig};
+
+    /// Serialize account fields behind the account discriminator
+    fn account_bytes<T: anchor_lang::Discriminator>(fields: &[u8], space: usize) -> Vec<u8> {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(fields);
+        data.resize(space, 0);
+        data
+    }
+
+    #[test]
+    fn user_balance_v1_upgrades_to_v2() {
+        let user = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+
+        // v1 layout: user, token_mint, balance, bump (no locked / version / reserved)
+        let mut fields = Vec::new();
+        fields.extend_from_slice(user.as_ref());
+        fields.extend_from_slice(token_mint.as_ref());
+        fields.extend_from_slice(&500u64.to_le_bytes());
+        fields.push(254);
+        let v1 = account_bytes::<UserBalance>(&fields, 8 + 32 + 32 + 8 + 1);
+
+        // v1 bytes do not fit the current layout without realloc
+        assert!(UserBalance::try_deserialize(&mut &v1[..]).is_err());
+
+        let mut upgraded: UserBalance = load_upgraded(&v1, 8 + UserBalance::INIT_SPACE).unwrap();
+        assert_eq!(upgraded.user, user);
+        assert_eq!(upgraded.token_mint, token_mint);
+        assert_eq!(upgraded.balance, 500);
+        assert_eq!(upgraded.bump, 254);
+        assert_eq!(upgraded.locked, 0);
+        assert_eq!(upgraded.version, 0);
+
+        upgraded.upgrade_version().unwrap();
+        assert_eq!(upgraded.version, CURRENT_ACCOUNT_VERSION);
+        assert!(upgraded.upgrade_version().is_err());
+
+        // Round trip through the v2 size
+        let mut v2 = Vec::new();
+        upgraded.try_serialize(&mut v2).unwrap();
+        assert!(v2.len() <= 8 + UserBalance::INIT_SPACE);
+        let reloaded = UserBalance::try_deserialize(&mut &v2[..]).unwrap();
+        assert_eq!(reloaded.balance, 500);
+        assert_eq!(reloaded.version, CURRENT_ACCOUNT_VERSION);
+    }
+
+    #[test]
+    fn vault_config_v1_with_full_trader_list_upgrades_to_v2() {
+        let admin = Pubkey::new_unique();
+        let emergency_admin = Pubkey::new_unique();
+        let traders: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+
+        // v1 layout: admin, emergency_admin, paused, traders (full), bump, pending admins
+        let mut fields = Vec::new();
+        fields.extend_from_slice(admin.as_ref());
+        fields.extend_from_slice(emergency_admin.as_ref());
+        fields.push(0);
+        fields.extend_from_slice(&(traders.len() as u32).to_le_bytes());
+        for trader in &traders {
+            fields.extend_from_slice(trader.as_ref());
+        }
+        fields.push(255);
+        fields.push(0);
+        fields.push(0);
+        let v1_space = 8 + 32 + 32 + 1 + 4 + (32 * 10) + 1 + (1 + 32) + (1 + 32);
+        let v1 = account_bytes::<VaultConfig>(&fields, v1_space);
+
+        let mut upgraded: VaultConfig = load_upgraded(&v1, 8 + VaultConfig::INIT_SPACE).unwrap();
+        assert_eq!(upgraded.admin, admin);
+        assert_eq!(upgraded.authorized_traders, traders);
+        assert_eq!(upgraded.pending_admin, None);
+        assert_eq!(upgraded.version, 0);
+
+        upgraded.upgrade_version().unwrap();
+        assert_eq!(upgraded.version, CURRENT_ACCOUNT_VERSION);
+    }
+}
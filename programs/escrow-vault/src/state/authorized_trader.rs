@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::error::VaultError;
+use crate::migration::CURRENT_ACCOUNT_VERSION;
+
+/// AuthorizedTrader - Per-program CPI authorization (PDA)
+/// Seeds: ["authorized_trader", trader_program]
+/// Replaces the capped `VaultConfig.authorized_traders` list (no limit on program count)
+#[account]
+pub struct AuthorizedTrader {
+    pub trader_program: Pubkey,                 // Program allowed to CPI into the vault (32 bytes)
+    pub operations: u8,                         // AuthorizedTrader::OP_* bitmask (1 byte)
+    pub signer: Pubkey,                         // ["vault_signer"] PDA of trader_program, must sign CPIs (32 bytes)
+    pub added_by: Pubkey,                       // Admin that authorized the program (32 bytes)
+    pub created_at: i64,                        // Authorization time (8 bytes)
+    pub updated_at: i64,                        // Last operations change (8 bytes)
+    pub bump: u8,                               // PDA bump (1 byte)
+    pub version: u8,                            // Layout version (1 byte)
+    pub reserved: [u8; 32],                     // Reserved for future fields (32 bytes)
+}
+
+impl AuthorizedTrader {
+    pub const AUTHORIZED_TRADER_SEED: &'static [u8] = b"authorized_trader";
+    /// Seed of the signer PDA each trader program derives under its own program id
+    pub const VAULT_SIGNER_SEED: &'static [u8] = b"vault_signer";
+
+    // Operation bits
+    pub const OP_SLASH: u8 = 1 << 0;                // slash_balance
+    pub const OP_CREDIT: u8 = 1 << 1;               // credit_balance
+    pub const OP_TRANSFER_OUT: u8 = 1 << 2;         // transfer_out (tokens leave the vault)
+    pub const OP_TRANSFER_BALANCE: u8 = 1 << 3;     // transfer_balance (user to user)
+    pub const OP_LOCK: u8 = 1 << 4;                 // lock_balance / unlock_balance
+    pub const OP_PAY_REWARD: u8 = 1 << 5;           // pay_reward (tokens leave the reward pool)
+    pub const ALL_OPERATIONS: u8 = Self::OP_SLASH
+        | Self::OP_CREDIT
+        | Self::OP_TRANSFER_OUT
+        | Self::OP_TRANSFER_BALANCE
+        | Self::OP_LOCK
+        | Self::OP_PAY_REWARD;
+
+    // Account space calculation: fields only (discriminator added at init)
+    pub const INIT_SPACE: usize = 32 + 1 + 32 + 32 + 8 + 8 + 1 + 1 + 32;
+
+    pub fn initialize(
+        &mut self,
+        trader_program: Pubkey,
+        operations: u8,
+        added_by: Pubkey,
+        current_time: i64,
+        bump: u8,
+    ) -> Result<()> {
+        self.trader_program = trader_program;
+        self.signer = Self::signer_address(&trader_program);
+        self.added_by = added_by;
+        self.created_at = current_time;
+        self.bump = bump;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.reserved = [0; 32];
+        self.set_operations(operations, current_time)
+    }
+
+    /// Replace allowed operations
+    pub fn set_operations(&mut self, operations: u8, current_time: i64) -> Result<()> {
+        require!(
+            operations != 0 && operations & !Self::ALL_OPERATIONS == 0,
+            VaultError::InvalidTraderOperations
+        );
+
+        self.operations = operations;
+        self.updated_at = current_time;
+        Ok(())
+    }
+
+    /// ["vault_signer"] PDA of `trader_program` - only that program can sign for it
+    pub fn signer_address(trader_program: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[Self::VAULT_SIGNER_SEED], trader_program).0
+    }
+
+    /// Check operation bit
+    pub fn allows(&self, operation: u8) -> bool {
+        self.operations & operation == operation
+    }
+}
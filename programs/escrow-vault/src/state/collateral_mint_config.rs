@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::error::VaultError;
+
+/// CollateralMintConfig - Admin-managed collateral allowlist entry (PDA)
+/// Seeds: ["collateral_mint_config", token_mint]
+#[account]
+pub struct CollateralMintConfig {
+    pub token_mint: Pubkey,     // Allowed collateral mint (32 bytes)
+    pub enabled: bool,          // Deposits / new matches allowed (1 byte)
+    pub deposit_cap: u64,       // Max total deposits, 0 = uncapped (8 bytes)
+    pub min_deposit: u64,       // Minimum single deposit (8 bytes)
+    pub decimals: u8,           // Mint decimals snapshot at configuration (1 byte)
+    pub updated_at: i64,        // Last configuration change (8 bytes)
+    pub bump: u8,               // PDA bump (1 byte)
+}
+
+impl CollateralMintConfig {
+    pub const COLLATERAL_MINT_CONFIG_SEED: &'static [u8] = b"collateral_mint_config";
+    
+    // Account space calculation: discriminator + fields
+    pub const INIT_SPACE: usize = 32 + 1 + 8 + 8 + 1 + 8 + 1;
+
+    /// Initialize collateral mint config
+    pub fn initialize(&mut self, token_mint: Pubkey, decimals: u8, bump: u8) {
+        self.token_mint = token_mint;
+        self.enabled = false;
+        self.deposit_cap = 0;
+        self.min_deposit = 0;
+        self.decimals = decimals;
+        self.updated_at = 0;
+        self.bump = bump;
+    }
+    
+    /// Validate a deposit against allowlist parameters
+    pub fn validate_deposit(&self, amount: u64, total_deposits: u64) -> Result<()> {
+        require!(self.enabled, VaultError::CollateralMintDisabled);
+        require!(amount >= self.min_deposit, VaultError::DepositBelowMinimum);
+        
+        if self.deposit_cap > 0 {
+            let new_total = total_deposits
+                .checked_add(amount)
+                .ok_or(VaultError::MathOverflow)?;
+            require!(new_total <= self.deposit_cap, VaultError::DepositCapExceeded);
+        }
+        
+        Ok(())
+    }
+}
@@ -1,7 +1,15 @@
 pub mod vault_config;
 pub mod user_balance;
 pub mod vault_authority;
+pub mod reward_pool;
+pub mod collateral_mint_config;
+pub mod authorized_trader;
+pub mod trader_outflow_limit;
 
 pub use vault_config::*;
 pub use user_balance::*;
-pub use vault_authority::*; 
\ No newline at end of file
+pub use vault_authority::*;
+pub use reward_pool::*;
+pub use collateral_mint_config::*;
+pub use authorized_trader::*;
+pub use trader_outflow_limit::*; 
\ No newline at end of file
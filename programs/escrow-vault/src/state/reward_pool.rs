@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::error::VaultError;
+
+/// RewardPool - Admin-funded settlement rewards per collateral mint (PDA)
+/// Seeds: ["reward_pool", token_mint]
+/// Tokens sit in the mint's vault ATA but are NOT part of VaultAuthority.total_deposits
+/// (mirrored in VaultAuthority.reward_pool_balance for solvency checks)
+#[account]
+pub struct RewardPool {
+    pub token_mint: Pubkey,     // Collateral mint this pool pays in (32 bytes)
+    pub balance: u64,           // Tokens available for rewards (8 bytes)
+    pub total_funded: u64,      // Lifetime amount funded by admin (8 bytes)
+    pub total_paid: u64,        // Lifetime rewards paid out (8 bytes)
+    pub bump: u8,               // PDA bump (1 byte)
+}
+
+impl RewardPool {
+    pub const REWARD_POOL_SEED: &'static [u8] = b"reward_pool";
+    
+    // Account space calculation: discriminator + fields
+    pub const INIT_SPACE: usize = 32 + 8 + 8 + 8 + 1;
+
+    /// Initialize reward pool
+    pub fn initialize(&mut self, token_mint: Pubkey, bump: u8) {
+        self.token_mint = token_mint;
+        self.balance = 0;
+        self.total_funded = 0;
+        self.total_paid = 0;
+        self.bump = bump;
+    }
+    
+    /// Add admin funding to the pool
+    pub fn fund(&mut self, amount: u64) -> Result<()> {
+        self.balance = self.balance
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        self.total_funded = self.total_funded
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Pay a reward out of the pool - refuses when underfunded
+    pub fn pay(&mut self, amount: u64) -> Result<()> {
+        require!(self.balance >= amount, VaultError::RewardPoolUnderfunded);
+        self.balance = self.balance
+            .checked_sub(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        self.total_paid = self.total_paid
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Check if pool can cover a reward
+    pub fn can_pay(&self, amount: u64) -> bool {
+        self.balance >= amount
+    }
+}
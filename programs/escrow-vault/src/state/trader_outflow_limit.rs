@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::error::VaultError;
+use crate::migration::CURRENT_ACCOUNT_VERSION;
+
+/// TraderOutflowLimit - Optional per-mint outflow limit of an authorized trader (PDA)
+/// Seeds: ["trader_outflow_limit", trader_program, token_mint]
+/// Caps tokens a trader program can move out of the vault (transfer_out / pay_reward)
+/// No account at the PDA = no limit for that program and mint
+#[account]
+pub struct TraderOutflowLimit {
+    pub trader_program: Pubkey,                 // Limited trader program (32 bytes)
+    pub token_mint: Pubkey,                     // Limited collateral mint (32 bytes)
+    pub max_per_transfer: u64,                  // Max single outflow, 0 = unlimited (8 bytes)
+    pub window_limit: u64,                      // Max outflow per window, 0 = unlimited (8 bytes)
+    pub window_duration: i64,                   // Window length in seconds (8 bytes)
+    pub window_start: i64,                      // Start of the current window (8 bytes)
+    pub window_outflow: u64,                    // Outflow in the current window (8 bytes)
+    pub total_outflow: u64,                     // Lifetime outflow (8 bytes)
+    pub updated_at: i64,                        // Last configuration change (8 bytes)
+    pub bump: u8,                               // PDA bump (1 byte)
+    pub version: u8,                            // Layout version (1 byte)
+    pub reserved: [u8; 32],                     // Reserved for future fields (32 bytes)
+}
+
+impl TraderOutflowLimit {
+    pub const TRADER_OUTFLOW_LIMIT_SEED: &'static [u8] = b"trader_outflow_limit";
+
+    // Account space calculation: fields only (discriminator added at init)
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 32;
+
+    pub fn initialize(&mut self, trader_program: Pubkey, token_mint: Pubkey, bump: u8) {
+        self.trader_program = trader_program;
+        self.token_mint = token_mint;
+        self.window_start = 0;
+        self.window_outflow = 0;
+        self.total_outflow = 0;
+        self.bump = bump;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.reserved = [0; 32];
+    }
+
+    /// Replace limit parameters (current window usage is kept)
+    pub fn configure(
+        &mut self,
+        max_per_transfer: u64,
+        window_limit: u64,
+        window_duration: i64,
+        current_time: i64,
+    ) -> Result<()> {
+        require!(
+            max_per_transfer > 0 || window_limit > 0,
+            VaultError::InvalidOutflowLimit
+        );
+        require!(
+            window_limit == 0 || window_duration > 0,
+            VaultError::InvalidOutflowLimit
+        );
+
+        self.max_per_transfer = max_per_transfer;
+        self.window_limit = window_limit;
+        self.window_duration = window_duration;
+        self.updated_at = current_time;
+        Ok(())
+    }
+
+    /// Validate and record an outflow, rolling the window when it has elapsed
+    pub fn record_outflow(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        if self.max_per_transfer > 0 {
+            require!(amount <= self.max_per_transfer, VaultError::OutflowLimitExceeded);
+        }
+
+        let window_end = self.window_start.saturating_add(self.window_duration);
+        if current_time >= window_end {
+            self.window_start = current_time;
+            self.window_outflow = 0;
+        }
+
+        let window_outflow = self.window_outflow
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        if self.window_limit > 0 {
+            require!(window_outflow <= self.window_limit, VaultError::OutflowLimitExceeded);
+        }
+
+        self.window_outflow = window_outflow;
+        self.total_outflow = self.total_outflow
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Enforce the limit stored at `account` if one is configured
+    /// `account` must already be constrained to the PDA of the caller and mint
+    pub fn enforce(account: &AccountInfo, amount: u64, current_time: i64) -> Result<()> {
+        if account.owner != &crate::ID || account.data_is_empty() {
+            return Ok(());
+        }
+
+        let mut data = account.try_borrow_mut_data()?;
+        let mut limit = TraderOutflowLimit::try_deserialize(&mut &data[..])?;
+        limit.record_outflow(amount, current_time)?;
+
+        let mut writer: &mut [u8] = &mut data;
+        limit.try_serialize(&mut writer)
+    }
+}
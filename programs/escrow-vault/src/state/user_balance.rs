@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::error::VaultError;
+use crate::migration::CURRENT_ACCOUNT_VERSION;
 
 /// UserBalance - Per user per token balance (PDA)
 /// Seeds: ["user_balance", user_pubkey, token_mint]
@@ -7,15 +8,18 @@ use crate::error::VaultError;
 pub struct UserBalance {
     pub user: Pubkey,           // User address (32 bytes)
     pub token_mint: Pubkey,     // Token mint address (32 bytes)
-    pub balance: u64,           // Available balance (8 bytes)
+    pub balance: u64,           // Free balance - withdrawable (8 bytes)
     pub bump: u8,               // PDA bump (1 byte)
+    pub locked: u64,            // Collateral committed to open trades (8 bytes)
+    pub version: u8,            // Layout version (1 byte)
+    pub reserved: [u8; 32],     // Reserved for future fields (32 bytes)
 }
 
 impl UserBalance {
     pub const USER_BALANCE_SEED: &'static [u8] = b"user_balance";
     
     // Account space calculation: discriminator + fields
-    pub const INIT_SPACE: usize = 32 + 32 + 8 + 1;
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 1 + 8 + 1 + 32;
 
     /// Initialize new user balance
     pub fn initialize(&mut self, user: Pubkey, token_mint: Pubkey, bump: u8) {
@@ -23,6 +27,16 @@ impl UserBalance {
         self.token_mint = token_mint;
         self.balance = 0;
         self.bump = bump;
+        self.locked = 0;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.reserved = [0; 32];
+    }
+    
+    /// Upgrade layout version after realloc (migrate_user_balance)
+    pub fn upgrade_version(&mut self) -> Result<()> {
+        require!(self.version < CURRENT_ACCOUNT_VERSION, VaultError::AlreadyMigrated);
+        self.version = CURRENT_ACCOUNT_VERSION;
+        Ok(())
     }
     
     /// Add to balance (exact EVM creditBalance mapping)
@@ -42,7 +56,38 @@ impl UserBalance {
         Ok(())
     }
     
-    /// Check if balance is sufficient
+    /// Move free balance into locked (collateral committed to a trade)
+    pub fn lock_balance(&mut self, amount: u64) -> Result<()> {
+        require!(self.balance >= amount, VaultError::InsufficientBalance);
+        self.balance = self.balance
+            .checked_sub(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        self.locked = self.locked
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Move locked balance back to free balance
+    pub fn unlock_balance(&mut self, amount: u64) -> Result<()> {
+        require!(self.locked >= amount, VaultError::InsufficientLockedBalance);
+        self.locked = self.locked
+            .checked_sub(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        self.balance = self.balance
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Free + locked balance
+    pub fn total_balance(&self) -> Result<u64> {
+        Ok(self.balance
+            .checked_add(self.locked)
+            .ok_or(VaultError::MathOverflow)?)
+    }
+    
+    /// Check if free balance is sufficient
     pub fn has_sufficient_balance(&self, amount: u64) -> bool {
         self.balance >= amount
     }
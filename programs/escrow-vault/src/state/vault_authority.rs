@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::error::VaultError;
+use crate::migration::CURRENT_ACCOUNT_VERSION;
 
 /// VaultAuthority - Token custody management (PDA)
 /// Seeds: ["vault_authority", token_mint]
@@ -9,13 +10,16 @@ pub struct VaultAuthority {
     pub vault_ata: Pubkey,      // Associated token account (32 bytes)
     pub total_deposits: u64,    // Total deposits for this token (8 bytes)
     pub bump: u8,               // PDA bump (1 byte)
+    pub version: u8,            // Layout version (1 byte)
+    pub reward_pool_balance: u64, // Reward pool tokens held by vault_ata - also a liability (8 bytes)
+    pub reserved: [u8; 24],     // Reserved for future fields (24 bytes)
 }
 
 impl VaultAuthority {
     pub const VAULT_AUTHORITY_SEED: &'static [u8] = b"vault_authority";
     
     // Account space calculation: discriminator + fields
-    pub const INIT_SPACE: usize = 32 + 32 + 8 + 1;
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 1 + 1 + 8 + 24;
 
     /// Initialize vault authority
     pub fn initialize(&mut self, token_mint: Pubkey, vault_ata: Pubkey, bump: u8) {
@@ -23,6 +27,16 @@ impl VaultAuthority {
         self.vault_ata = vault_ata;
         self.total_deposits = 0;
         self.bump = bump;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.reward_pool_balance = 0;
+        self.reserved = [0; 24];
+    }
+    
+    /// Upgrade layout version after realloc (migrate_vault_authority)
+    pub fn upgrade_version(&mut self) -> Result<()> {
+        require!(self.version < CURRENT_ACCOUNT_VERSION, VaultError::AlreadyMigrated);
+        self.version = CURRENT_ACCOUNT_VERSION;
+        Ok(())
     }
     
     /// Add to total deposits (exact EVM logic)
@@ -41,4 +55,33 @@ impl VaultAuthority {
             .ok_or(VaultError::MathOverflow)?;
         Ok(())
     }
+    
+    /// Track reward pool funding held in vault_ata
+    pub fn add_reward_liability(&mut self, amount: u64) -> Result<()> {
+        self.reward_pool_balance = self.reward_pool_balance
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Track reward paid out of vault_ata
+    pub fn subtract_reward_liability(&mut self, amount: u64) -> Result<()> {
+        self.reward_pool_balance = self.reward_pool_balance
+            .checked_sub(amount)
+            .ok_or(VaultError::RewardPoolUnderfunded)?;
+        Ok(())
+    }
+    
+    /// Tokens vault_ata owes: user deposits + reward pool
+    pub fn liabilities(&self) -> Result<u64> {
+        self.total_deposits
+            .checked_add(self.reward_pool_balance)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+    
+    /// Refuse state where user deposits + reward pool exceed token reserves held by vault_ata
+    pub fn ensure_solvent(&self, reserves: u64) -> Result<()> {
+        require!(reserves >= self.liabilities()?, VaultError::ReservesInsufficient);
+        Ok(())
+    }
 } 
\ No newline at end of file
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::error::VaultError;
+use crate::migration::CURRENT_ACCOUNT_VERSION;
+use crate::state::AuthorizedTrader;
 
 /// VaultConfig - Global vault state (PDA)
 /// Seeds: ["vault_config"]
@@ -8,15 +10,20 @@ pub struct VaultConfig {
     pub admin: Pubkey,                          // 32 bytes
     pub emergency_admin: Pubkey,                // 32 bytes
     pub paused: bool,                           // 1 byte
-    pub authorized_traders: Vec<Pubkey>,        // 4 + (32 * n) bytes
+    pub authorized_traders: Vec<Pubkey>,        // Legacy list, superseded by AuthorizedTrader PDAs (4 + (32 * n) bytes)
     pub bump: u8,                               // 1 byte
+    pub pending_admin: Option<Pubkey>,          // 1 + 32 bytes
+    pub pending_emergency_admin: Option<Pubkey>, // 1 + 32 bytes
+    pub version: u8,                            // Layout version (1 byte)
+    pub trader_count: u32,                      // AuthorizedTrader PDAs (4 bytes)
+    pub reserved: [u8; 60],                     // Reserved for future fields (60 bytes)
 }
 
 impl VaultConfig {
     pub const VAULT_CONFIG_SEED: &'static [u8] = b"vault_config";
     
-    // Maximum space allocation (for up to 10 traders, no supported tokens limit)  
-    pub const INIT_SPACE: usize = 32 + 32 + 1 + 4 + (32 * 10) + 1;
+    // Maximum space allocation (legacy list sized for 10 traders, no supported tokens limit)  
+    pub const INIT_SPACE: usize = 32 + 32 + 1 + 4 + (32 * 10) + 1 + (1 + 32) + (1 + 32) + 1 + 4 + 60;
 
     pub fn initialize(&mut self, admin: Pubkey, emergency_admin: Pubkey, bump: u8) {
         self.admin = admin;
@@ -24,103 +31,267 @@ impl VaultConfig {
         self.paused = false;
         self.authorized_traders = Vec::new();
         self.bump = bump;
+        self.pending_admin = None;
+        self.pending_emergency_admin = None;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.trader_count = 0;
+        self.reserved = [0; 60];
     }
 
-    pub fn is_authorized_trader(&self, trader_program: &Pubkey) -> bool {
-        self.authorized_traders.contains(trader_program)
+    /// Upgrade layout version after realloc (migrate_vault_config)
+    pub fn upgrade_version(&mut self) -> Result<()> {
+        require!(self.version < CURRENT_ACCOUNT_VERSION, VaultError::AlreadyMigrated);
+        self.version = CURRENT_ACCOUNT_VERSION;
+        Ok(())
+    }
+
+    /// Propose new admin (step 1 of admin transfer)
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        require!(
+            new_admin != Pubkey::default() && new_admin != self.admin,
+            VaultError::InvalidPendingAdmin
+        );
+        
+        self.pending_admin = Some(new_admin);
+        Ok(())
     }
 
-    pub fn add_authorized_trader(&mut self, trader_program: Pubkey) -> Result<()> {
+    /// Accept admin role (step 2 of admin transfer) - returns previous admin
+    pub fn accept_admin(&mut self, new_admin: Pubkey) -> Result<Pubkey> {
         require!(
-            !self.authorized_traders.contains(&trader_program),
-            VaultError::TraderAlreadyAuthorized
+            self.pending_admin == Some(new_admin),
+            VaultError::InvalidPendingAdmin
         );
         
+        let previous_admin = self.admin;
+        self.admin = new_admin;
+        self.pending_admin = None;
+        Ok(previous_admin)
+    }
+
+    /// Cancel pending admin transfer - returns cancelled admin
+    pub fn cancel_admin_transfer(&mut self) -> Result<Pubkey> {
+        let cancelled_admin = self.pending_admin
+            .take()
+            .ok_or(VaultError::NoPendingAdmin)?;
+        Ok(cancelled_admin)
+    }
+
+    /// Propose new emergency admin (step 1 of emergency admin rotation)
+    pub fn propose_emergency_admin(&mut self, new_emergency_admin: Pubkey) -> Result<()> {
         require!(
-            self.authorized_traders.len() < 10,
-            VaultError::MaximumTradersReached
+            new_emergency_admin != Pubkey::default()
+                && new_emergency_admin != self.emergency_admin,
+            VaultError::InvalidPendingAdmin
         );
         
-        self.authorized_traders.push(trader_program);
+        self.pending_emergency_admin = Some(new_emergency_admin);
         Ok(())
     }
 
-    pub fn remove_authorized_trader(&mut self, trader_program: &Pubkey) -> Result<()> {
-        let position = self.authorized_traders
-            .iter()
-            .position(|&x| x == *trader_program)
-            .ok_or(VaultError::TraderNotFound)?;
+    /// Accept emergency admin role (step 2) - returns previous emergency admin
+    pub fn accept_emergency_admin(&mut self, new_emergency_admin: Pubkey) -> Result<Pubkey> {
+        require!(
+            self.pending_emergency_admin == Some(new_emergency_admin),
+            VaultError::InvalidPendingAdmin
+        );
         
-        self.authorized_traders.remove(position);
+        let previous_emergency_admin = self.emergency_admin;
+        self.emergency_admin = new_emergency_admin;
+        self.pending_emergency_admin = None;
+        Ok(previous_emergency_admin)
+    }
+
+    /// Cancel pending emergency admin rotation - returns cancelled emergency admin
+    pub fn cancel_emergency_admin_transfer(&mut self) -> Result<Pubkey> {
+        let cancelled = self.pending_emergency_admin
+            .take()
+            .ok_or(VaultError::NoPendingAdmin)?;
+        Ok(cancelled)
+    }
+
+    /// Track AuthorizedTrader PDA creation
+    pub fn increment_trader_count(&mut self) -> Result<()> {
+        self.trader_count = self.trader_count
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
         Ok(())
     }
 
+    /// Track AuthorizedTrader PDA removal
+    pub fn decrement_trader_count(&mut self) {
+        self.trader_count = self.trader_count.saturating_sub(1);
+    }
+
     /// ✅ STANDARD CPI VALIDATION - Basic validation
     pub fn validate_cpi_caller(&self) -> Result<()> {
         require!(!self.paused, VaultError::VaultPaused);
         Ok(())
     }
 
+    /// Caller must sign with the ["vault_signer"] PDA recorded on its AuthorizedTrader
+    /// (only the trader program can produce that signature via invoke_signed)
+    /// and be granted `operation` - returns the authenticated caller program
+    fn authorize_trader(
+        caller_signer: &AccountInfo,
+        authorized_trader: &AuthorizedTrader,
+        operation: u8,
+    ) -> Result<Pubkey> {
+        require!(
+            caller_signer.is_signer && caller_signer.key() == authorized_trader.signer,
+            VaultError::UnauthorizedTrader
+        );
+        require!(
+            authorized_trader.allows(operation),
+            VaultError::TraderOperationNotPermitted
+        );
+        Ok(authorized_trader.trader_program)
+    }
+
     /// 🔍 DEBUG CPI VALIDATION - With detailed logging
-    pub fn validate_cpi_caller_with_logging(&self, caller_program: &Pubkey, operation: &str) -> Result<()> {
-        msg!("🔍 CPI Validation Debug for {}", operation);
-        msg!("📞 Caller Program: {}", caller_program);
-        msg!("👥 Authorized Traders Count: {}", self.authorized_traders.len());
-        
-        for (i, trader) in self.authorized_traders.iter().enumerate() {
-            msg!("  {}. {}", i + 1, trader);
-        }
+    pub fn validate_cpi_caller_with_logging(
+        &self,
+        caller_signer: &AccountInfo,
+        authorized_trader: &AuthorizedTrader,
+        operation: u8,
+    ) -> Result<Pubkey> {
+        msg!("🔍 CPI Validation Debug for operation {:#08b}", operation);
+        msg!("📞 Caller Signer: {} (signed: {})", caller_signer.key(), caller_signer.is_signer);
+        msg!("👥 Authorized Trader: {}", authorized_trader.trader_program);
+        msg!("🔑 Expected Signer: {}", authorized_trader.signer);
+        msg!("🔑 Allowed Operations: {:#08b}", authorized_trader.operations);
         
         require!(!self.paused, VaultError::VaultPaused);
         
-        require!(
-            self.is_authorized_trader(caller_program),
-            VaultError::UnauthorizedTrader
-        );
+        let caller_program = Self::authorize_trader(caller_signer, authorized_trader, operation)?;
         
-        msg!("✅ CPI Validation passed for {}", operation);
-        Ok(())
+        msg!("✅ CPI Validation passed for operation {:#08b}", operation);
+        Ok(caller_program)
     }
 
-    /// 🛡️ PRECISE CPI VALIDATION - Using instruction sysvar detection
-    /// This is the most accurate method for CPI caller validation
-    pub fn validate_cpi_caller_precise(&self, caller_program_id: &Pubkey, operation: &str) -> Result<()> {
+    /// 🛡️ PRECISE CPI VALIDATION - Using the caller's signer PDA
+    /// The caller is proven by signature, not inferred from transaction instructions
+    pub fn validate_cpi_caller_precise(
+        &self,
+        caller_signer: &AccountInfo,
+        authorized_trader: &AuthorizedTrader,
+        operation: u8,
+    ) -> Result<Pubkey> {
         // Validate vault is not paused
         require!(!self.paused, VaultError::VaultPaused);
         
-        // Validate caller is authorized using precise detection
-        require!(
-            self.is_authorized_trader(caller_program_id),
-            VaultError::UnauthorizedTrader
-        );
-        
-        Ok(())
+        // Validate caller signature and operation grant
+        Self::authorize_trader(caller_signer, authorized_trader, operation)
     }
 
     /// 🚀 ADVANCED CPI VALIDATION - With additional security features
-    pub fn validate_cpi_caller_advanced(&self, caller_program: &Pubkey, operation: &str) -> Result<()> {
+    pub fn validate_cpi_caller_advanced(
+        &self,
+        caller_signer: &AccountInfo,
+        authorized_trader: &AuthorizedTrader,
+        operation: u8,
+    ) -> Result<Pubkey> {
         // Basic validations
         require!(!self.paused, VaultError::VaultPaused);
         
         // Authorization check
-        require!(
-            self.is_authorized_trader(caller_program),
-            VaultError::UnauthorizedTrader
-        );
+        let caller_program = Self::authorize_trader(caller_signer, authorized_trader, operation)?;
         
         // Additional security: Check if caller is not a system program
         require!(
-            *caller_program != solana_program::system_program::ID,
+            caller_program != solana_program::system_program::ID,
             VaultError::UnauthorizedTrader
         );
         
         // Additional security: Check if caller is not a token program
         require!(
-            *caller_program != anchor_spl::token::ID,
+            caller_program != anchor_spl::token::ID,
             VaultError::UnauthorizedTrader
         );
         
-        msg!("🚀 Advanced CPI Validation passed for {}: caller={}", operation, caller_program);
-        Ok(())
+        msg!("🚀 Advanced CPI Validation passed for {:#08b}: caller={}", operation, caller_program);
+        Ok(caller_program)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault_config() -> VaultConfig {
+        VaultConfig {
+            admin: Pubkey::new_unique(),
+            emergency_admin: Pubkey::new_unique(),
+            paused: false,
+            authorized_traders: Vec::new(),
+            bump: 255,
+            pending_admin: None,
+            pending_emergency_admin: None,
+            version: CURRENT_ACCOUNT_VERSION,
+            trader_count: 1,
+            reserved: [0; 60],
+        }
     }
-} 
\ No newline at end of file
+
+    fn authorized_trader(trader_program: Pubkey, operations: u8) -> AuthorizedTrader {
+        AuthorizedTrader {
+            trader_program,
+            operations,
+            signer: AuthorizedTrader::signer_address(&trader_program),
+            added_by: Pubkey::new_unique(),
+            created_at: 0,
+            updated_at: 0,
+            bump: 255,
+            version: CURRENT_ACCOUNT_VERSION,
+            reserved: [0; 32],
+        }
+    }
+
+    /// Run `f` with an AccountInfo for `key`
+    fn with_caller<R>(key: Pubkey, is_signer: bool, f: impl FnOnce(&AccountInfo) -> R) -> R {
+        let owner = Pubkey::default();
+        let mut lamports = 0;
+        let mut data = [];
+        let account = AccountInfo::new(&key, is_signer, false, &mut lamports, &mut data, &owner, false, 0);
+        f(&account)
+    }
+
+    #[test]
+    fn signer_pda_of_authorized_program_is_accepted() {
+        let trader_program = Pubkey::new_unique();
+        let trader = authorized_trader(trader_program, AuthorizedTrader::ALL_OPERATIONS);
+
+        let caller = with_caller(trader.signer, true, |signer| {
+            vault_config().validate_cpi_caller_precise(signer, &trader, AuthorizedTrader::OP_SLASH)
+        });
+        assert_eq!(caller.unwrap(), trader_program);
+    }
+
+    #[test]
+    fn direct_call_without_signer_pda_signature_is_rejected() {
+        let trader = authorized_trader(Pubkey::new_unique(), AuthorizedTrader::ALL_OPERATIONS);
+        let config = vault_config();
+
+        // Correct PDA passed without its signature (only the trader program can sign)
+        let unsigned = with_caller(trader.signer, false, |signer| {
+            config.validate_cpi_caller_precise(signer, &trader, AuthorizedTrader::OP_TRANSFER_OUT)
+        });
+        assert_eq!(unsigned.unwrap_err(), VaultError::UnauthorizedTrader.into());
+
+        // Attacker-controlled signer
+        let attacker = with_caller(Pubkey::new_unique(), true, |signer| {
+            config.validate_cpi_caller_precise(signer, &trader, AuthorizedTrader::OP_TRANSFER_OUT)
+        });
+        assert_eq!(attacker.unwrap_err(), VaultError::UnauthorizedTrader.into());
+    }
+
+    #[test]
+    fn operation_outside_grant_is_rejected() {
+        let trader = authorized_trader(Pubkey::new_unique(), AuthorizedTrader::OP_LOCK);
+
+        let result = with_caller(trader.signer, true, |signer| {
+            vault_config().validate_cpi_caller_precise(signer, &trader, AuthorizedTrader::OP_TRANSFER_OUT)
+        });
+        assert_eq!(result.unwrap_err(), VaultError::TraderOperationNotPermitted.into());
+    }
+}
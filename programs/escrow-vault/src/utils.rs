@@ -1,39 +1,65 @@
 use anchor_lang::prelude::*;
-use solana_program::sysvar::instructions::{self, load_instruction_at_checked};
-use solana_program::pubkey;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    state::Mint as MintState,
+};
 use crate::error::VaultError;
 
-/// 🔍 Shared utility to extract caller program ID from instruction sysvar
-/// This is the most accurate way to detect CPI caller in Solana
-/// Handles system programs (ComputeBudget, System, etc.) by skipping them
-pub fn get_cpi_caller_program_id(instruction_sysvar: &AccountInfo) -> Result<Pubkey> {
-    let system_programs = [
-        solana_program::system_program::ID,
-        pubkey!("ComputeBudget111111111111111111111111111111"),
-        anchor_lang::solana_program::sysvar::ID,
-    ];
+/// Token-2022 mint extensions that are safe for pooled vault custody
+/// Anything else (permanent delegate, transfer hook, non-transferable,
+/// confidential transfers, default frozen state...) is rejected
+pub const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 5] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MintCloseAuthority,
+    ExtensionType::InterestBearingConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+/// 🔍 Check mint only carries supported extensions (always true for SPL Token mints)
+pub fn is_supported_mint(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(true);
+    }
     
-    // Get current instruction index
-    let current_index = instructions::load_current_index_checked(instruction_sysvar)
-        .map_err(|_| VaultError::FailedToLoadInstruction)?;
-    msg!("Current instruction index: {}", current_index);
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let extensions = state.get_extension_types()?;
+    
+    Ok(extensions.iter().all(|ext| SUPPORTED_MINT_EXTENSIONS.contains(ext)))
+}
+
+/// 💸 Transfer fee withheld when sending `amount` (0 without TransferFeeConfig)
+pub fn calculate_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(0);
+    }
     
-    // Search ALL previous instructions (0 to current_index-1)
-    // But also try to search beyond current_index if needed
-    for i in (0..=current_index + 1).rev() { // ✅ FIXED: search more broadly
-        msg!("Checking instruction at index {}", i);
-        if let Ok(instruction) = load_instruction_at_checked(i as usize, instruction_sysvar) {
-            msg!("Instruction program ID: {}", instruction.program_id);
-            if !system_programs.contains(&instruction.program_id) {
-                msg!("Found caller program at index {}: {}", i, instruction.program_id);
-                return Ok(instruction.program_id);
-            } else {
-                msg!("Skipping system program at index {}: {}", i, instruction.program_id);
-            }
-        } else {
-            msg!("Failed to load instruction at index {}", i);
-        }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    match state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(VaultError::MathOverflow.into()),
+        Err(_) => Ok(0),
+    }
+}
+
+/// 💸 Transfer fee to add on top so that the recipient receives exactly `net_amount`
+pub fn calculate_inverse_transfer_fee(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(0);
     }
     
-    Err(VaultError::FailedToLoadInstruction.into())
-}
\ No newline at end of file
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    match state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+            .ok_or(VaultError::MathOverflow.into()),
+        Err(_) => Ok(0),
+    }
+}
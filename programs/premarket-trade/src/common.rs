@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::error::TradingError;
 
 // Program IDs and constants
 pub const VAULT_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
@@ -29,10 +30,24 @@ pub const MAX_PRICE: u64 = 1_000_000_000_000_000_000; // 1e18
 pub const MAX_COLLATERAL_RATIO: u16 = 20000; // 200%
 pub const MAX_REWARD_BPS: u16 = 1000; // 10%
 pub const MAX_PENALTY_BPS: u16 = 10000; // 100%
+pub const MAX_FEE_BPS: u16 = 1000; // 10%
+pub const MAX_ORDER_AMOUNT: u64 = 1_000_000_000_000_000; // 1e15
+
+// Governance constants
+pub const MIN_CONFIG_CHANGE_DELAY: i64 = 86_400; // Queued config changes take effect >= 24 hours out
+pub const TOKEN_REMAP_DELAY: i64 = 21_600; // Queued token remaps take effect 6 hours out
 
 // Technical limits
 pub const MAX_SYMBOL_LENGTH: usize = 10;
 pub const MAX_NAME_LENGTH: usize = 50;
+pub const MAX_ACCEPTED_COLLATERAL: usize = 5;
+
+// Account layout versioning (shared with vault program)
+pub use escrow_vault::migration::CURRENT_ACCOUNT_VERSION;
+
+/// Seed of this program's signer PDA - signs every CPI into the vault
+/// (registered as `AuthorizedTrader.signer` on the vault side)
+pub const VAULT_SIGNER_SEED: &[u8] = escrow_vault::state::AuthorizedTrader::VAULT_SIGNER_SEED;
 
 /// PreOrder - Off-chain signed order (Updated for Keypair Pattern)
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -56,6 +71,59 @@ pub struct EconomicConfig {
     pub seller_collateral_ratio: u16,   // Default: 10000 (100%)
     pub seller_reward_bps: u16,         // Default: 0 (0%)
     pub late_penalty_bps: u16,          // Default: 10000 (100%)
+    pub buyer_fee_bps: u16,             // Default: 0 (0%) - charged at match
+    pub seller_fee_bps: u16,            // Default: 0 (0%) - charged at settlement
+}
+
+impl EconomicConfig {
+    /// Validate bounds (shared by TradeConfig and per-market overrides)
+    pub fn validate(&self) -> Result<()> {
+        // Collateral ratios (0-200%)
+        require!(
+            self.buyer_collateral_ratio <= MAX_COLLATERAL_RATIO,
+            TradingError::InvalidCollateralRatio
+        );
+        require!(
+            self.seller_collateral_ratio <= MAX_COLLATERAL_RATIO,
+            TradingError::InvalidCollateralRatio
+        );
+        
+        // Seller reward (0-10%) and late penalty (0-100%)
+        require!(
+            self.seller_reward_bps <= MAX_REWARD_BPS,
+            TradingError::InvalidRewardParameters
+        );
+        require!(
+            self.late_penalty_bps <= MAX_PENALTY_BPS,
+            TradingError::InvalidRewardParameters
+        );
+        
+        // Protocol fees (0-10%)
+        require!(
+            self.buyer_fee_bps <= MAX_FEE_BPS,
+            TradingError::InvalidFeeParameters
+        );
+        require!(
+            self.seller_fee_bps <= MAX_FEE_BPS,
+            TradingError::InvalidFeeParameters
+        );
+        
+        // Order amount limits
+        require!(
+            self.minimum_fill_amount > 0,
+            TradingError::ZeroAmount
+        );
+        require!(
+            self.maximum_order_amount > self.minimum_fill_amount,
+            TradingError::InvalidFillAmount
+        );
+        require!(
+            self.maximum_order_amount <= MAX_ORDER_AMOUNT,
+            TradingError::ExceedOrderAmount
+        );
+        
+        Ok(())
+    }
 }
 
 impl Default for EconomicConfig {
@@ -65,17 +133,38 @@ impl Default for EconomicConfig {
             seller_collateral_ratio: 10000, // 100%
             seller_reward_bps: 0,           // 0%
             late_penalty_bps: 10000,        // 100%
+            buyer_fee_bps: 0,               // 0%
+            seller_fee_bps: 0,              // 0%
             minimum_fill_amount: 1000,      // 0.001 tokens
             maximum_order_amount: 1_000_000_000_000, // 1M tokens
         }
     }
 }
 
+/// Relayer registration parameters (RelayerInfo)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RelayerParams {
+    pub permissions: u8,                // RelayerInfo::PERMISSION_* bitmask
+    pub market: Option<Pubkey>,         // Restrict to one TokenMarket, None = all markets
+    pub active_from: i64,               // Not usable before this time
+    pub expires_at: i64,                // Not usable after this time, 0 = no expiry
+}
+
+/// Token remap parameters (QueuedTokenRemap)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenRemapParams {
+    pub real_mint: Pubkey,              // Corrected real mint
+    pub conversion_numerator: u64,      // Corrected pre-market → real base unit ratio
+    pub conversion_denominator: u64,
+    pub reason: RemapReason,            // Reason code published in events
+}
+
 /// Technical Config
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct TechnicalConfig {
     pub min_settle_time: u32,           // Default: 3600 seconds (1 hour)
     pub max_settle_time: u32,           // Default: 2592000 seconds (30 days)
+    pub trade_record_retention: u32,    // Default: 604800 seconds (7 days) before close
 }
 
 impl Default for TechnicalConfig {
@@ -83,10 +172,79 @@ impl Default for TechnicalConfig {
         Self {
             min_settle_time: 30,      // 30 seconds
             max_settle_time: 2592000,   // 30 days
+            trade_record_retention: 604800, // 7 days
+        }
+    }
+}
+
+/// Queued configuration change (applied by execute_config_change after the delay)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum ConfigChange {
+    Economic(EconomicConfig),
+    Technical(TechnicalConfig),
+    MarketEconomic(Option<EconomicConfig>), // Market override, None = clear (execute_market_config_change)
+}
+
+impl ConfigChange {
+    pub const ECONOMIC: u8 = 0;
+    pub const TECHNICAL: u8 = 1;
+    pub const MARKET_ECONOMIC: u8 = 2;
+
+    /// Seed byte of the QueuedConfigChange PDA (one queued change per kind, per market for overrides)
+    pub fn kind(&self) -> u8 {
+        match self {
+            ConfigChange::Economic(_) => Self::ECONOMIC,
+            ConfigChange::Technical(_) => Self::TECHNICAL,
+            ConfigChange::MarketEconomic(_) => Self::MARKET_ECONOMIC,
         }
     }
 }
 
+/// Where settlement proceeds are paid to the seller
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettlementPayout {
+    VaultBalance,   // Credit seller's vault UserBalance (reusable as collateral)
+    Wallet,         // Transfer out to seller's collateral ATA
+}
+
+/// TokenMarket lifecycle: Listed → Trading → Settlement → Closed, Voided from any open state
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketStatus {
+    Listed,         // Created or halted - no matching
+    Trading,        // Orders can be matched
+    Settlement,     // Token mapped - trades settle / cancel, no new matches
+    Closed,         // Settlement window over - only buyer cancellation of leftover trades
+    Voided,         // Launch cancelled - no settlement or cancellation
+}
+
+impl MarketStatus {
+    /// Check admin transition is allowed (mapping requirement checked by TokenMarket)
+    pub fn can_transition_to(&self, next: MarketStatus) -> bool {
+        matches!(
+            (self, next),
+            (MarketStatus::Listed, MarketStatus::Trading)
+                | (MarketStatus::Trading, MarketStatus::Listed)
+                | (MarketStatus::Listed | MarketStatus::Trading, MarketStatus::Settlement)
+                | (MarketStatus::Settlement, MarketStatus::Closed)
+                | (MarketStatus::Listed | MarketStatus::Trading | MarketStatus::Settlement, MarketStatus::Voided)
+        )
+    }
+
+    /// Closed and Voided markets never change status again
+    pub fn is_final(&self) -> bool {
+        matches!(self, MarketStatus::Closed | MarketStatus::Voided)
+    }
+}
+
+/// Why a mapped token was corrected (remap_token)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemapReason {
+    WrongMint,              // Mapped to the wrong mint address
+    WrongConversionRatio,   // Mint correct, ratio / decimals wrong
+    TokenMigration,         // Project moved to a new mint before delivery
+    Other,
+}
+
 #[error_code]
 pub enum SharedError {
     #[msg("Math overflow")]
@@ -114,6 +272,15 @@ pub fn safe_calculate_collateral(
     Ok(collateral)
 }
 
+/// Calculate protocol fee (basis points of trade value)
+pub fn safe_calculate_fee(
+    amount: u64,
+    price: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    safe_calculate_collateral(amount, price, fee_bps)
+}
+
 /// Create order message for signature verification
 pub fn create_order_message(order: &PreOrder) -> Vec<u8> {
     let mut message = Vec::new();
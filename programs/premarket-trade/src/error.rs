@@ -188,4 +188,100 @@ pub enum TradingError {
     
     #[msg("Invalid instruction sysvar account")]
     InvalidInstructionSysvar,
+    
+    #[msg("Order signature instruction not found")]
+    MissingOrderSignature,
+    
+    #[msg("Nonce must be greater than current minimum")]
+    InvalidNonce,
+    
+    #[msg("Order nonce below trader minimum")]
+    OrderNonceInvalidated,
+    
+    #[msg("Invalid fee parameters")]
+    InvalidFeeParameters,
+    
+    #[msg("Invalid treasury")]
+    InvalidTreasury,
+    
+    #[msg("Reward pool account not provided")]
+    RewardPoolNotProvided,
+    
+    #[msg("Invalid settlement amount")]
+    InvalidSettlementAmount,
+    
+    #[msg("Trade not settled")]
+    TradeNotSettled,
+    
+    #[msg("Trade record retention period still active")]
+    RetentionPeriodActive,
+    
+    #[msg("Invalid retention period")]
+    InvalidRetentionPeriod,
+    
+    #[msg("Rent payer does not match trade record")]
+    InvalidRentPayer,
+    
+    #[msg("Invalid pending admin")]
+    InvalidPendingAdmin,
+    
+    #[msg("No pending admin transfer")]
+    NoPendingAdmin,
+    
+    #[msg("Unsupported token mint extension")]
+    UnsupportedMintExtension,
+    
+    #[msg("Invalid accepted collateral list")]
+    InvalidAcceptedCollateral,
+    
+    #[msg("Collateral mint not allowed")]
+    CollateralMintNotAllowed,
+    
+    #[msg("Account already migrated")]
+    AlreadyMigrated,
+    
+    #[msg("Invalid relayer permissions")]
+    InvalidRelayerPermissions,
+    
+    #[msg("Relayer not active")]
+    RelayerNotActive,
+    
+    #[msg("Relayer not authorized for this market")]
+    RelayerMarketMismatch,
+    
+    #[msg("Config change must take effect after the minimum delay")]
+    ConfigChangeDelayTooShort,
+    
+    #[msg("Config change not yet effective")]
+    ConfigChangeNotReady,
+    
+    #[msg("Operation not allowed in current market status")]
+    InvalidMarketStatus,
+    
+    #[msg("Invalid market status transition")]
+    InvalidMarketStatusTransition,
+    
+    #[msg("Invalid conversion ratio")]
+    InvalidConversionRatio,
+    
+    #[msg("Amount not exactly convertible to real token units")]
+    LossyConversion,
+    
+    #[msg("Market already has settled trades")]
+    MarketHasSettledTrades,
+    
+    #[msg("Token remap not yet effective")]
+    TokenRemapNotReady,
+    
+    #[msg("Config change kind not handled by this instruction")]
+    InvalidConfigChangeKind,
+    
+    #[msg("Settlement window still open")]
+    SettlementWindowOpen,
+    
+    #[msg("Token remap pending for this market")]
+    TokenRemapPending,
+    
+    #[msg("Trade config must be migrated first")]
+    TradeConfigNotMigrated,
 } 
\ No newline at end of file
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::common::{EconomicConfig, TechnicalConfig};
+use crate::common::{ConfigChange, EconomicConfig, MarketStatus, RemapReason, TechnicalConfig, SettlementPayout};
 
 /// Trading system initialized
 #[event]
@@ -18,6 +18,7 @@ pub struct TokenMarketCreated {
     pub name: String,               // Token name
     pub settle_time_limit: u32,     // Grace period in seconds
     pub created_at: i64,            // Creation timestamp
+    pub accepted_collateral: Vec<Pubkey>, // Collateral mints accepted for matches
 }
 
 /// Token mapped to real mint (Admin only)
@@ -26,23 +27,94 @@ pub struct TokenMapped {
     pub token_id: Pubkey,           // Account address as token ID (EVM compatible naming)
     pub real_mint: Pubkey,          // Real token mint address
     pub mapping_time: i64,          // When token was mapped
+    pub real_mint_decimals: u8,     // Real mint decimals snapshot
+    pub conversion_numerator: u64,  // Real base units per `conversion_denominator` pre-market units
+    pub conversion_denominator: u64,
+    pub settle_time_limit: u32,     // Trade deadline = max(match_time, mapping_time, settlement_started_at) + settle_time_limit
 }
 
-/// Relayer added to authorized list (Admin only)
+/// Token mapping correction queued - applies after TOKEN_REMAP_DELAY
+#[event]
+pub struct TokenRemapScheduled {
+    pub token_id: Pubkey,           // Account address as token ID (EVM compatible naming)
+    pub current_real_mint: Pubkey,  // Mint mapped today
+    pub new_real_mint: Pubkey,      // Corrected mint
+    pub conversion_numerator: u64,  // Corrected conversion ratio
+    pub conversion_denominator: u64,
+    pub reason: RemapReason,
+    pub admin: Pubkey,
+    pub scheduled_at: i64,
+    pub effective_at: i64,          // Earliest execution time
+}
+
+/// Queued token mapping correction discarded
+#[event]
+pub struct TokenRemapCancelled {
+    pub token_id: Pubkey,
+    pub real_mint: Pubkey,          // Discarded mint
+    pub reason: RemapReason,
+    pub admin: Pubkey,
+    pub cancelled_at: i64,
+}
+
+/// Token mapping corrected - settlement window restarts at `mapping_time`
+#[event]
+pub struct TokenRemapped {
+    pub token_id: Pubkey,           // Account address as token ID (EVM compatible naming)
+    pub previous_real_mint: Pubkey, // Wrong mint
+    pub real_mint: Pubkey,          // Corrected mint
+    pub real_mint_decimals: u8,     // Corrected mint decimals snapshot
+    pub conversion_numerator: u64,
+    pub conversion_denominator: u64,
+    pub reason: RemapReason,
+    pub admin: Pubkey,              // Admin who scheduled the remap
+    pub executed_by: Pubkey,        // Account that executed the queued remap
+    pub mapping_time: i64,          // New mapping time - restarts every open trade's settlement window
+    pub settle_time_limit: u32,     // Trade deadline = max(match_time, mapping_time, settlement_started_at) + settle_time_limit
+}
+
+/// Order signature requirement changed for a market (Admin only)
+#[event]
+pub struct OrderSignatureModeUpdated {
+    pub token_id: Pubkey,                   // Account address as token ID (EVM compatible naming)
+    pub admin: Pubkey,                      // Admin who changed the mode
+    pub require_order_signatures: bool,     // false = relayer-trust mode
+    pub timestamp: i64,                     // When mode was changed
+}
+
+/// Relayer registered via RelayerInfo PDA (Admin only)
 #[event]
 pub struct RelayerAdded {
     pub admin: Pubkey,              // Admin who added relayer
     pub relayer: Pubkey,            // Relayer address added
-    pub total_relayers: u8,         // Total number of relayers after addition
+    pub permissions: u8,            // RelayerInfo::PERMISSION_* bitmask
+    pub market: Option<Pubkey>,     // Market scope (None = all markets)
+    pub active_from: i64,           // Activation time
+    pub expires_at: i64,            // Expiry time (0 = no expiry)
+    pub total_relayers: u32,        // Total number of relayers after addition
     pub timestamp: i64,             // When relayer was added
 }
 
-/// Relayer removed from authorized list (Admin only)
+/// Relayer permissions / scope / window updated (Admin only)
+#[event]
+pub struct RelayerUpdated {
+    pub admin: Pubkey,
+    pub relayer: Pubkey,
+    pub permissions: u8,
+    pub market: Option<Pubkey>,
+    pub active_from: i64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+/// Relayer removed and RelayerInfo closed (Admin only)
 #[event]
 pub struct RelayerRemoved {
     pub admin: Pubkey,              // Admin who removed relayer
     pub relayer: Pubkey,            // Relayer address removed
-    pub total_relayers: u8,         // Total number of relayers after removal
+    pub matches_count: u64,         // Lifetime matches executed
+    pub matched_amount: u64,        // Lifetime token amount filled
+    pub total_relayers: u32,        // Total number of relayers after removal
     pub timestamp: i64,             // When relayer was removed
 }
 
@@ -58,9 +130,14 @@ pub struct OrdersMatched {
     pub price: u64,                 // Price per token (6 decimals)
     pub buyer_collateral: u64,      // Buyer collateral locked
     pub seller_collateral: u64,     // Seller collateral locked
+    pub buyer_fee: u64,             // Protocol fee collected from buyer at match
+    pub seller_fee: u64,            // Protocol fee due from seller at settlement
     pub match_time: i64,            // When trade was matched
+    pub settlement_deadline: i64,   // Provisional deadline at match, 0 = token not mapped yet - restarts at Settlement (see MarketStatusChanged)
     pub buy_order_hash: String,     // Buy order hash (hex format) - human readable
     pub sell_order_hash: String,    // Sell order hash (hex format) - human readable
+    pub trade_hash: String,         // TradeRecord PDA seed (hex format) - generate_trade_id
+    pub fill_offset: u64,           // TradeRecord PDA seed - buy order filled_quantity before this fill
 }
 
 /// Order placed
@@ -81,10 +158,19 @@ pub struct OrderCancelled {
     pub order_hash: [u8; 32],          // Order hash for identification
     pub trader: Pubkey,                // Order creator
     pub token_id: Pubkey,              // TokenMarket account address as token ID
-    pub collateral_released: u64,      // Collateral returned to vault balance
+    pub cancelled_amount: u64,         // Unfilled quantity that can no longer be matched
     pub cancellation_time: i64,        // When cancellation occurred
 }
 
+/// Trader invalidated all orders below a nonce
+#[event]
+pub struct OrdersCancelledBelowNonce {
+    pub trader: Pubkey,                // Trader wallet
+    pub previous_min_nonce: u64,       // Floor before the update
+    pub min_nonce: u64,                // New floor - orders below it can no longer match
+    pub timestamp: i64,                // When the floor was raised
+}
+
 /// Trade settled (Updated to match business requirements)
 #[event]
 pub struct TradeSettled {
@@ -93,9 +179,20 @@ pub struct TradeSettled {
     pub buyer: Pubkey,              // Buyer wallet
     pub seller: Pubkey,             // Seller wallet
     pub target_mint: Pubkey,        // Real token mint that was delivered
-    pub filled_amount: u64,         // Amount of tokens delivered
-    pub seller_reward: u64,         // Reward earned by seller
+    pub filled_amount: u64,         // Pre-market amount settled in this settlement
+    pub delivered_amount: u64,      // Real token base units delivered (filled_amount converted)
+    pub delivery_fee: u64,          // Token-2022 transfer fee paid by seller on delivery
+    pub total_settled_amount: u64,  // Cumulative tokens delivered for the trade
+    pub remaining_amount: u64,      // Tokens still outstanding (0 = fully settled)
+    pub buyer_payment: u64,         // Buyer locked collateral moved to seller
+    pub seller_collateral_released: u64, // Seller collateral unlocked
+    pub seller_payout: u64,         // Proceeds to seller after seller fee
+    pub payout: SettlementPayout,   // Vault balance or wallet
+    pub seller_reward: u64,         // Reward paid to seller from reward pool
+    pub unpaid_reward: u64,         // Reward refused because pool was missing/underfunded
+    pub seller_fee: u64,            // Protocol fee paid to treasury
     pub settlement_time: i64,       // When settlement occurred
+    pub settlement_deadline: i64,   // max(match_time, mapping_time, settlement_started_at) + settle_time_limit
 }
 
 /// Trade cancelled (Updated to match business requirements)
@@ -108,6 +205,49 @@ pub struct TradeCancelled {
     pub penalty_amount: u64,        // Penalty transferred from seller to buyer
     pub cancellation_time: i64,     // When cancellation occurred
     pub collateral_mint: Pubkey,    // Collateral token mint address
+    pub cancelled_amount: u64,      // Unsettled tokens cancelled (penalty base)
+    pub settled_amount: u64,        // Tokens already delivered via partial settlement
+    pub settlement_deadline: i64,   // Deadline that was missed
+}
+
+/// Trade of a voided market refunded - collateral returned to both sides, no penalty
+#[event]
+pub struct TradeRefunded {
+    pub trade_id: Pubkey,           // Account address as trade ID (EVM compatible naming)
+    pub token_id: Pubkey,           // Account address as token ID (EVM compatible naming)
+    pub buyer: Pubkey,              // Buyer wallet
+    pub seller: Pubkey,             // Seller wallet
+    pub collateral_mint: Pubkey,    // Collateral token mint address
+    pub refunded_amount: u64,       // Unsettled tokens unwound
+    pub buyer_refund: u64,          // Buyer collateral unlocked
+    pub seller_refund: u64,         // Seller collateral unlocked
+    pub settled_amount: u64,        // Tokens already delivered via partial settlement
+    pub cranker: Pubkey,            // Who cranked the refund
+    pub refund_time: i64,           // When refund occurred
+}
+
+/// Trade record closed - full final state kept for indexers
+#[event]
+pub struct TradeRecordArchived {
+    pub trade_id: Pubkey,           // Account address as trade ID (EVM compatible naming)
+    pub token_id: Pubkey,           // Account address as token ID (EVM compatible naming)
+    pub buyer: Pubkey,              // Buyer wallet
+    pub seller: Pubkey,             // Seller wallet
+    pub collateral_mint: Pubkey,    // Collateral token mint address
+    pub filled_amount: u64,         // Amount filled
+    pub price: u64,                 // Price per token (6 decimals)
+    pub buyer_collateral: u64,      // Buyer collateral locked at match
+    pub seller_collateral: u64,     // Seller collateral locked at match
+    pub match_time: i64,            // When trade was matched
+    pub settlement_deadline: i64,   // Deadline recorded on the trade (snapshot, not authoritative)
+    pub buyer_fee: u64,             // Protocol fee paid by buyer
+    pub seller_fee: u64,            // Protocol fee owed by seller
+    pub settled_amount: u64,        // Tokens delivered (< filled_amount if cancelled)
+    pub settled_at: i64,            // When trade was settled or cancelled
+    pub rent_payer: Pubkey,         // Rent refund recipient
+    pub rent_refunded: u64,         // Lamports returned to rent payer
+    pub closer: Pubkey,             // Who cranked the close
+    pub closed_at: i64,             // When record was closed
 }
 
 /// Trading configuration updated
@@ -133,10 +273,20 @@ pub struct TradingUnpaused {
     pub timestamp: i64,
 }
 
+/// Protocol fee recipient updated
+#[event]
+pub struct TreasuryUpdated {
+    pub admin: Pubkey,              // Admin who updated the treasury
+    pub old_treasury: Pubkey,       // Previous fee recipient
+    pub new_treasury: Pubkey,       // New fee recipient
+    pub updated_at: i64,            // When update occurred
+}
+
 /// Economic configuration updated
 #[event]
 pub struct EconomicConfigUpdated {
-    pub admin: Pubkey,              // Admin who updated the config
+    pub admin: Pubkey,              // Admin who scheduled the config
+    pub executed_by: Pubkey,        // Account that executed the queued change
     pub old_config: EconomicConfig,  // Previous configuration
     pub new_config: EconomicConfig,  // New configuration
     pub updated_at: i64,            // When update occurred
@@ -145,8 +295,112 @@ pub struct EconomicConfigUpdated {
 /// Technical configuration updated
 #[event]
 pub struct TechnicalConfigUpdated {
-    pub admin: Pubkey,              // Admin who updated the config
+    pub admin: Pubkey,              // Admin who scheduled the config
+    pub executed_by: Pubkey,        // Account that executed the queued change
     pub old_config: TechnicalConfig, // Previous configuration
     pub new_config: TechnicalConfig, // New configuration
     pub updated_at: i64,            // When update occurred
-} 
\ No newline at end of file
+}
+
+/// Configuration change queued - traders can react before `effective_at`
+#[event]
+pub struct PendingConfigChange {
+    pub admin: Pubkey,              // Admin who scheduled the change
+    pub change: ConfigChange,       // New economic or technical configuration
+    pub scheduled_at: i64,          // When the change was queued
+    pub effective_at: i64,          // Earliest execution time
+}
+
+/// Queued configuration change cancelled before execution
+#[event]
+pub struct PendingConfigChangeCancelled {
+    pub admin: Pubkey,              // Admin who cancelled the change
+    pub change: ConfigChange,       // Discarded configuration
+    pub effective_at: i64,          // Execution time it was queued for
+    pub cancelled_at: i64,
+}
+
+/// Admin transfer proposed (Admin only)
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,              // Current admin
+    pub pending_admin: Pubkey,      // Proposed admin (must accept)
+    pub timestamp: i64,
+}
+
+/// Admin transfer accepted by pending admin
+#[event]
+pub struct AdminTransferAccepted {
+    pub previous_admin: Pubkey,     // Replaced admin
+    pub new_admin: Pubkey,          // New admin
+    pub timestamp: i64,
+}
+
+/// Pending admin transfer cancelled (Admin only)
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,              // Current admin
+    pub cancelled_admin: Pubkey,    // Proposal that was withdrawn
+    pub timestamp: i64,
+}
+
+/// Accepted collateral mints updated for a market (Admin only)
+#[event]
+pub struct AcceptedCollateralUpdated {
+    pub token_id: Pubkey,
+    pub admin: Pubkey,
+    pub accepted_collateral: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+/// Market lifecycle status changed (Admin only)
+#[event]
+pub struct MarketStatusChanged {
+    pub token_id: Pubkey,
+    pub previous_status: MarketStatus,
+    pub new_status: MarketStatus,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Market-level economic override queued - traders can react before `effective_at`
+#[event]
+pub struct PendingMarketConfigChange {
+    pub token_id: Pubkey,
+    pub admin: Pubkey,                         // Admin who scheduled the change
+    pub new_override: Option<EconomicConfig>,  // None = clear, fall back to TradeConfig defaults
+    pub scheduled_at: i64,
+    pub effective_at: i64,                     // Earliest execution time
+}
+
+/// Queued market-level economic override cancelled before execution
+#[event]
+pub struct PendingMarketConfigChangeCancelled {
+    pub token_id: Pubkey,
+    pub admin: Pubkey,                         // Admin who cancelled the change
+    pub new_override: Option<EconomicConfig>,  // Discarded override
+    pub effective_at: i64,
+    pub cancelled_at: i64,
+}
+
+/// Market-level economic override set or cleared after the timelock
+#[event]
+pub struct MarketEconomicConfigUpdated {
+    pub token_id: Pubkey,
+    pub admin: Pubkey,                         // Admin who scheduled the change
+    pub executed_by: Pubkey,                   // Account that executed the queued change
+    pub old_override: Option<EconomicConfig>,  // None = TradeConfig defaults applied
+    pub new_override: Option<EconomicConfig>,
+    pub timestamp: i64,
+}
+
+/// Versioned account upgraded in place by a migrate_* instruction
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub account_type: String,       // TradeConfig / TokenMarket / TradeRecord / OrderStatus
+    pub previous_version: u8,       // 0 = unversioned (v1) layout
+    pub version: u8,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
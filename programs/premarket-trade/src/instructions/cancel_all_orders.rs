@@ -0,0 +1,79 @@
+/*!
+ * # CANCEL ALL ORDERS INSTRUCTION
+ * 
+ * ## 🎯 Business Purpose
+ * Lets a trader invalidate every outstanding order in one transaction by raising
+ * their minimum valid nonce (EVM-style "cancel all below nonce").
+ * 
+ * ## 🔄 Flow
+ * 1. **NonceState**: Create the trader's NonceState PDA on first use
+ * 2. **Floor Update**: Require the new floor to be strictly higher
+ * 3. **Event Emission**: Emit OrdersCancelledBelowNonce
+ * 
+ * ## 🛡️ Security Requirements
+ * - Only the trader can raise their own floor
+ * - The floor can never decrease, so invalidated orders stay invalid
+ * - Allowed while trading is paused so quotes can always be pulled
+ * 
+ * ## 🔗 Enforcement
+ * match_orders and cancel_order reject any order with `nonce < min_nonce`
+ */
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::events::OrdersCancelledBelowNonce;
+
+#[derive(Accounts)]
+pub struct CancelAllOrders<'info> {
+    /// Trader NonceState PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = 8 + NonceState::INIT_SPACE,
+        seeds = [
+            NonceState::NONCE_STATE_SEED,
+            trader.key().as_ref()
+        ],
+        bump,
+    )]
+    pub nonce_state: Account<'info, NonceState>,
+    
+    /// Trader raising their nonce floor
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CancelAllOrders>,
+    min_nonce: u64,
+) -> Result<()> {
+    let nonce_state = &mut ctx.accounts.nonce_state;
+    let trader = ctx.accounts.trader.key();
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Initialize NonceState if new
+    if nonce_state.trader == Pubkey::default() {
+        nonce_state.initialize(trader, ctx.bumps.nonce_state);
+    }
+    
+    let previous_min_nonce = nonce_state.min_nonce;
+    nonce_state.raise_min_nonce(min_nonce, current_time)?;
+    
+    emit!(OrdersCancelledBelowNonce {
+        trader,
+        previous_min_nonce,
+        min_nonce,
+        timestamp: current_time,
+    });
+    
+    msg!(
+        "All orders below nonce {} cancelled for trader: {} (previous floor: {})",
+        min_nonce,
+        trader,
+        previous_min_nonce
+    );
+    
+    Ok(())
+}
@@ -7,27 +7,30 @@
  * 
  * ## 🔄 Cancellation Flow
  * 1. **Validation**: Check buyer authority, grace period expired, trade not settled
- * 2. **Penalty Calculation**: Calculate penalty from seller collateral
- * 3. **Buyer Payout**: Transfer buyer collateral + penalty to buyer wallet
- * 4. **Seller Payout**: Transfer remaining seller collateral to seller wallet
+ * 2. **Penalty Calculation**: Calculate penalty on the unsettled remainder only
+ * 3. **Unlock**: Unlock buyer and seller collateral, move penalty from seller to buyer balance
+ * 4. **Payout**: Transfer buyer collateral + penalty and remaining seller collateral to wallets
  * 5. **State Update**: Mark trade as settled (cancelled)
  * 6. **Event Emission**: Emit TradeCancelled event
  * 
  * ## 🛡️ Security Requirements
  * - Only buyer can cancel their own trades
  * - Cancellation only allowed after grace period expires
+ * - Grace period starts at max(match_time, mapping_time, settlement_started_at) - never cancellable while unmapped
  * - Trade must not be already settled
  * - All collateral distributions via CPI to vault program
  * 
  * ## 💰 Economic Model
- * - Buyer gets: `buyer_collateral + penalty_amount`
- * - Seller gets: `seller_collateral - penalty_amount` (if positive)
- * - Penalty = `trade_value * late_penalty_bps / 10000`
+ * - Only the unsettled remainder is cancelled; chunks already delivered via
+ *   `settle_partial` keep their released amounts
+ * - Buyer gets: `unsettled buyer_collateral + penalty_amount`
+ * - Seller gets: `unsettled seller_collateral - penalty_amount` (if positive)
+ * - Penalty = `unsettled_value * late_penalty_bps / 10000` (rate snapshotted on the TradeRecord at match)
  * - All transfers go directly to external wallets (exact EVM logic)
  * 
  * ## 🔗 Cross-Program Integration
  * - Uses CPI to vault program for collateral distribution
- * - No credit_balance() - direct transfers only (matches EVM)
+ * - unlock_balance() releases collateral locked at match before transfer_out
  * - Follows exact EVM cancelAfterGracePeriod() logic
  * 
  * ## 📊 Event Data
@@ -35,8 +38,9 @@
  */
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
+use crate::common::VAULT_SIGNER_SEED;
 use crate::error::TradingError;
 use crate::events::TradeCancelled;
 
@@ -46,7 +50,7 @@ use escrow_vault::program::EscrowVault;
 
 #[derive(Accounts)]
 pub struct CancelTrade<'info> {
-    /// TradeRecord account to cancel (User-controlled keypair)
+    /// TradeRecord PDA to cancel
     #[account(
         mut,
         constraint = trade_record.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
@@ -59,9 +63,21 @@ pub struct CancelTrade<'info> {
     #[account(
         constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
         constraint = token_market.token_id == trade_record.token_id @ TradingError::TokenMintMismatch,
+        constraint = token_market.is_mapped() @ TradingError::TokenNotMapped,
+        constraint = token_market.allows_trade_cancellation() @ TradingError::InvalidMarketStatus,
     )]
     pub token_market: Account<'info, TokenMarket>,
     
+    /// QueuedTokenRemap PDA of the market - must not exist (the remap restarts settlement windows
+    /// and sellers cannot settle meanwhile)
+    /// CHECK: Seeds pin the address, only emptiness is checked
+    #[account(
+        seeds = [QueuedTokenRemap::QUEUED_TOKEN_REMAP_SEED, token_market.key().as_ref()],
+        bump,
+        constraint = queued_remap.data_is_empty() @ TradingError::TokenRemapPending,
+    )]
+    pub queued_remap: UncheckedAccount<'info>,
+    
     /// Trade configuration PDA for economic parameters
     #[account(
         seeds = [TradeConfig::TRADE_CONFIG_SEED],
@@ -89,14 +105,56 @@ pub struct CancelTrade<'info> {
     )]
     pub vault_config: Account<'info, escrow_vault::state::VaultConfig>,
     
-    /// Buyer balance PDA for collateral release
-    /// CHECK: Buyer balance account validated via CPI to vault program
+    /// Vault AuthorizedTrader PDA of this program (CPI authorization)
+    #[account(
+        seeds = [
+            escrow_vault::state::AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            crate::ID.as_ref()
+        ],
+        bump = authorized_trader.bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub authorized_trader: Box<Account<'info, escrow_vault::state::AuthorizedTrader>>,
+    
+    /// Program signer PDA - signs vault CPIs (matched against AuthorizedTrader.signer)
+    /// CHECK: PDA of this program, holds no data
+    #[account(
+        seeds = [VAULT_SIGNER_SEED],
+        bump,
+    )]
+    pub vault_signer: UncheckedAccount<'info>,
+    
+    /// Vault outflow limit of this program for the collateral mint (may not exist)
+    /// CHECK: PDA derivation validated via CPI to vault program
     #[account(mut)]
+    pub outflow_limit: AccountInfo<'info>,
+    
+    /// Buyer balance PDA for collateral release
+    /// CHECK: Seeds pin the account to trade_record.buyer, contents validated via CPI
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::UserBalance::USER_BALANCE_SEED,
+            trade_record.buyer.as_ref(),
+            trade_record.collateral_mint.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
     pub buyer_balance: AccountInfo<'info>,
     
     /// Seller balance PDA for collateral release
-    /// CHECK: Seller balance account validated via CPI to vault program
-    #[account(mut)]
+    /// CHECK: Seeds pin the account to trade_record.seller, contents validated via CPI
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::UserBalance::USER_BALANCE_SEED,
+            trade_record.seller.as_ref(),
+            trade_record.collateral_mint.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
     pub seller_balance: AccountInfo<'info>,
     
     /// Vault authority PDA
@@ -116,7 +174,7 @@ pub struct CancelTrade<'info> {
         mut,
         constraint = vault_ata.mint == trade_record.collateral_mint @ TradingError::TokenMintMismatch,
     )]
-    pub vault_ata: Account<'info, TokenAccount>,
+    pub vault_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// Buyer ATA for collateral return
     #[account(
@@ -124,7 +182,7 @@ pub struct CancelTrade<'info> {
         constraint = buyer_collateral_ata.owner == buyer.key() @ TradingError::InvalidAccountOwner,
         constraint = buyer_collateral_ata.mint == trade_record.collateral_mint @ TradingError::TokenMintMismatch,
     )]
-    pub buyer_collateral_ata: Account<'info, TokenAccount>,
+    pub buyer_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// Seller ATA for remaining collateral return
     #[account(
@@ -132,46 +190,81 @@ pub struct CancelTrade<'info> {
         constraint = seller_collateral_ata.owner == trade_record.seller @ TradingError::InvalidAccountOwner,
         constraint = seller_collateral_ata.mint == trade_record.collateral_mint @ TradingError::TokenMintMismatch,
     )]
-    pub seller_collateral_ata: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub seller_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// 🛡️ INSTRUCTION SYSVAR - For precise CPI caller detection
-    /// CHECK: Validated by constraint to ensure it's the instruction sysvar
+    /// Collateral mint (required by vault transfer_checked)
     #[account(
-        constraint = instruction_sysvar.key() == solana_program::sysvar::instructions::ID @ TradingError::InvalidInstructionSysvar
+        address = trade_record.collateral_mint @ TradingError::TokenMintMismatch,
+        mint::token_program = token_program,
     )]
-    pub instruction_sysvar: AccountInfo<'info>,
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<CancelTrade>) -> Result<()> {
     let trade_record = &ctx.accounts.trade_record;
     let token_market = &ctx.accounts.token_market;
-    let config = &ctx.accounts.config;
     
     // Get current time for validation
     let current_time = Clock::get()?.unix_timestamp;
     
-    // Validate grace period has expired (cancellation only allowed after grace period)
-    let grace_period_end = trade_record.match_time + (token_market.settle_time_limit as i64);
+    // Validate grace period has expired (window starts at max(match_time, mapping_time, settlement_started_at))
+    let settlement_deadline = trade_record
+        .effective_settlement_deadline(token_market)
+        .ok_or(TradingError::TokenNotMapped)?;
     require!(
-        current_time > grace_period_end,
+        current_time > settlement_deadline,
         TradingError::GracePeriodActive
     );
 
-    msg!("Economic config: {:?}", config.economic_config);
+    msg!("Late penalty (snapshot at match): {} bps", trade_record.late_penalty_bps);
+    
+    // Only the unsettled remainder is cancelled (partially settled chunks are final)
+    let unsettled_amount = trade_record.remaining_amount();
+    let buyer_collateral = trade_record.pro_rata_share(
+        trade_record.buyer_collateral,
+        trade_record.settled_amount,
+        trade_record.filled_amount,
+    )?;
+    let seller_collateral = trade_record.pro_rata_share(
+        trade_record.seller_collateral,
+        trade_record.settled_amount,
+        trade_record.filled_amount,
+    )?;
     
     // Calculate penalty distribution
     let (penalty_amount, buyer_total, seller_remaining) = calculate_cancellation_amounts(
-        trade_record.filled_amount,
+        unsettled_amount,
         trade_record.price,
-        trade_record.buyer_collateral,
-        trade_record.seller_collateral,
-        &config.economic_config,
+        buyer_collateral,
+        seller_collateral,
+        trade_record.late_penalty_bps,
     )?;
     
-    // Step 1: Transfer buyer collateral + penalty to buyer wallet
+    // Step 1: Unlock collateral of the unsettled remainder and move penalty to buyer
+    // (pre-upgrade trades had collateral slashed at match - credit it back instead)
+    if trade_record.legacy_collateral {
+        if buyer_collateral > 0 {
+            credit_buyer_collateral_cpi(&ctx, buyer_collateral)?;
+        }
+        if seller_collateral > 0 {
+            credit_seller_collateral_cpi(&ctx, seller_collateral)?;
+        }
+    } else {
+        if buyer_collateral > 0 {
+            unlock_buyer_collateral_cpi(&ctx, buyer_collateral)?;
+        }
+        if seller_collateral > 0 {
+            unlock_seller_collateral_cpi(&ctx, seller_collateral)?;
+        }
+    }
+    if penalty_amount > 0 {
+        transfer_penalty_cpi(&ctx, penalty_amount)?;
+    }
+    
+    // Step 2: Transfer buyer collateral + penalty to buyer wallet
     if buyer_total > 0 {
         msg!(
             "Transferring {} (collateral + penalty) to buyer via CPI",
@@ -181,7 +274,7 @@ pub fn handler(ctx: Context<CancelTrade>) -> Result<()> {
         transfer_collateral_to_buyer_cpi(&ctx, buyer_total)?;
     }
     
-    // Step 2: Transfer remaining seller collateral to seller wallet (if any)
+    // Step 3: Transfer remaining seller collateral to seller wallet (if any)
     if seller_remaining > 0 {
         msg!(
             "Transferring {} remaining collateral to seller via CPI",
@@ -191,11 +284,13 @@ pub fn handler(ctx: Context<CancelTrade>) -> Result<()> {
         transfer_collateral_to_seller_cpi(&ctx, seller_remaining)?;
     }
     
-    // Step 3: Update trade record state
+    // Step 4: Update trade record state
     let trade_record = &mut ctx.accounts.trade_record;
     trade_record.settled = true;
+    trade_record.settled_at = current_time;
+    trade_record.settlement_deadline = settlement_deadline;
     
-    // Step 4: Emit TradeCancelled event
+    // Step 5: Emit TradeCancelled event
     emit!(TradeCancelled {
         trade_id: trade_record.trade_id,
         token_id: trade_record.token_id,        // EVM compatible naming
@@ -204,13 +299,17 @@ pub fn handler(ctx: Context<CancelTrade>) -> Result<()> {
         penalty_amount,
         cancellation_time: current_time,
         collateral_mint: trade_record.collateral_mint,
+        cancelled_amount: unsettled_amount,
+        settled_amount: trade_record.settled_amount,
+        settlement_deadline,
     });
     
     msg!(
-        "Trade cancelled successfully: trade_id: {} - buyer: {} - seller: {} - penalty: {}",
+        "Trade cancelled successfully: trade_id: {} - buyer: {} - seller: {} - unsettled: {} - penalty: {}",
         trade_record.trade_id,
         trade_record.buyer,
         trade_record.seller,
+        unsettled_amount,
         penalty_amount
     );
     
@@ -218,15 +317,16 @@ pub fn handler(ctx: Context<CancelTrade>) -> Result<()> {
 }
 
 /// Calculate cancellation amounts: penalty, buyer total, seller remaining
+/// All inputs cover only the unsettled remainder of the trade
 fn calculate_cancellation_amounts(
-    filled_amount: u64,
+    unsettled_amount: u64,
     price: u64,
     buyer_collateral: u64,
     seller_collateral: u64,
-    economic_config: &crate::common::EconomicConfig,
+    late_penalty_bps: u16,
 ) -> Result<(u64, u64, u64)> {
-    // Calculate trade value
-    let trade_value = filled_amount
+    // Calculate value of the undelivered tokens
+    let trade_value = unsettled_amount
         .checked_mul(price)
         .ok_or(TradingError::MathOverflow)?
         .checked_div(crate::common::PRICE_SCALE)
@@ -234,7 +334,7 @@ fn calculate_cancellation_amounts(
     
     // Calculate penalty amount (from seller to buyer)
     let penalty_amount = trade_value
-        .checked_mul(economic_config.late_penalty_bps as u64)
+        .checked_mul(late_penalty_bps as u64)
         .ok_or(TradingError::MathOverflow)?
         .checked_div(10000)
         .ok_or(TradingError::MathOverflow)?;
@@ -253,6 +353,127 @@ fn calculate_cancellation_amounts(
     Ok((actual_penalty, buyer_total, seller_remaining))
 }
 
+/// Unlock buyer collateral locked at match via CPI to vault program
+fn unlock_buyer_collateral_cpi(
+    ctx: &Context<CancelTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::UnlockBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.buyer_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::unlock_balance(cpi_ctx, amount)?;
+    
+    msg!("Buyer collateral unlocked successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Unlock seller collateral locked at match via CPI to vault program
+fn unlock_seller_collateral_cpi(
+    ctx: &Context<CancelTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::UnlockBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.seller_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::unlock_balance(cpi_ctx, amount)?;
+    
+    msg!("Seller collateral unlocked successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Credit buyer collateral of a pre-upgrade trade (slashed at match) via CPI to vault program
+fn credit_buyer_collateral_cpi(
+    ctx: &Context<CancelTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::CreditBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.buyer_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::credit_balance(cpi_ctx, amount)?;
+    
+    msg!("Legacy buyer collateral credited successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Credit seller collateral of a pre-upgrade trade (slashed at match) via CPI to vault program
+fn credit_seller_collateral_cpi(
+    ctx: &Context<CancelTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::CreditBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.seller_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::credit_balance(cpi_ctx, amount)?;
+    
+    msg!("Legacy seller collateral credited successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Move late penalty from seller to buyer balance via CPI to vault program
+fn transfer_penalty_cpi(
+    ctx: &Context<CancelTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::TransferBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        from_balance: ctx.accounts.seller_balance.to_account_info(),
+        to_balance: ctx.accounts.buyer_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::transfer_balance(
+        cpi_ctx,
+        ctx.accounts.trade_record.seller,
+        ctx.accounts.trade_record.buyer,
+        amount,
+    )?;
+    
+    msg!("Penalty moved from seller to buyer successfully via CPI: {}", amount);
+    Ok(())
+}
+
 /// Transfer buyer collateral + penalty via CPI to vault program
 fn transfer_collateral_to_buyer_cpi(
     ctx: &Context<CancelTrade>,
@@ -263,16 +484,20 @@ fn transfer_collateral_to_buyer_cpi(
     // All accounts from same Context - unified lifetime
     let cpi_accounts = cpi::accounts::TransferOut {
         config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
         user_balance: ctx.accounts.buyer_balance.to_account_info(),
         vault_authority: ctx.accounts.vault_authority.to_account_info(),
         vault_token_account: ctx.accounts.vault_ata.to_account_info(),
+        token_mint: ctx.accounts.collateral_mint.to_account_info(),
         recipient_token_account: ctx.accounts.buyer_collateral_ata.to_account_info(),
+        outflow_limit: ctx.accounts.outflow_limit.to_account_info(),
         token_program: ctx.accounts.token_program.to_account_info(),
-        instruction_sysvar: ctx.accounts.instruction_sysvar.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
     };
     
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
     let cpi_program = ctx.accounts.vault_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
     
     // Execute CPI call to transfer tokens from vault to buyer wallet
     cpi::transfer_out(cpi_ctx, ctx.accounts.buyer.key(), amount)?;
@@ -291,20 +516,58 @@ fn transfer_collateral_to_seller_cpi(
     // All accounts from same Context - unified lifetime
     let cpi_accounts = cpi::accounts::TransferOut {
         config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
         user_balance: ctx.accounts.seller_balance.to_account_info(),
         vault_authority: ctx.accounts.vault_authority.to_account_info(),
         vault_token_account: ctx.accounts.vault_ata.to_account_info(),
+        token_mint: ctx.accounts.collateral_mint.to_account_info(),
         recipient_token_account: ctx.accounts.seller_collateral_ata.to_account_info(),
+        outflow_limit: ctx.accounts.outflow_limit.to_account_info(),
         token_program: ctx.accounts.token_program.to_account_info(),
-        instruction_sysvar: ctx.accounts.instruction_sysvar.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
     };
     
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
     let cpi_program = ctx.accounts.vault_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
     
     // Execute CPI call to transfer tokens from vault to seller wallet
     cpi::transfer_out(cpi_ctx, ctx.accounts.trade_record.seller, amount)?;
     
     msg!("Remaining seller collateral transferred successfully via CPI: {}", amount);
     Ok(())
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PRICE_SCALE;
+
+    #[test]
+    fn cancellation_penalty_is_charged_on_the_undelivered_value() {
+        // 400 undelivered tokens at 2.5 = 1_000 value, 10% penalty
+        let (penalty, buyer_total, seller_remaining) =
+            calculate_cancellation_amounts(400, 5 * PRICE_SCALE / 2, 600, 900, 1_000).unwrap();
+        assert_eq!(penalty, 100);
+        assert_eq!(buyer_total, 700);
+        assert_eq!(seller_remaining, 800);
+    }
+
+    #[test]
+    fn cancellation_penalty_is_capped_at_seller_collateral() {
+        let (penalty, buyer_total, seller_remaining) =
+            calculate_cancellation_amounts(400, 5 * PRICE_SCALE / 2, 600, 50, 10_000).unwrap();
+        assert_eq!(penalty, 50);
+        assert_eq!(buyer_total, 650);
+        assert_eq!(seller_remaining, 0);
+    }
+
+    #[test]
+    fn cancellation_without_penalty_returns_both_collaterals() {
+        let (penalty, buyer_total, seller_remaining) =
+            calculate_cancellation_amounts(400, 5 * PRICE_SCALE / 2, 600, 900, 0).unwrap();
+        assert_eq!(penalty, 0);
+        assert_eq!(buyer_total, 600);
+        assert_eq!(seller_remaining, 900);
+    }
+}
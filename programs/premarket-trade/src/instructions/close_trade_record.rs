@@ -0,0 +1,101 @@
+/*!
+ * # CLOSE TRADE RECORD INSTRUCTION
+ * 
+ * ## 🎯 Business Purpose
+ * Reclaims the rent of a finished TradeRecord so relayer SOL is not locked
+ * forever in historical trades. Permissionless crank: rent always goes back
+ * to the payer recorded on the TradeRecord at match time.
+ * 
+ * ## 🔄 Close Flow
+ * 1. **Validation**: Trade settled/cancelled, retention delay elapsed, rent payer matches
+ * 2. **Event Emission**: Emit TradeRecordArchived with the full final state
+ * 3. **Close**: Anchor closes the account and refunds rent to the recorded payer
+ * 
+ * ## 🛡️ Security Requirements
+ * - Only fully settled or cancelled trades can be closed
+ * - `technical_config.trade_record_retention` seconds must pass after settlement
+ * - Rent can only be refunded to `trade_record.rent_payer`
+ * 
+ * ## 📊 Event Data
+ * Emits `TradeRecordArchived` so indexers keep the record after the account is gone
+ */
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::TradingError;
+use crate::events::TradeRecordArchived;
+
+#[derive(Accounts)]
+pub struct CloseTradeRecord<'info> {
+    /// TradeRecord account to close (rent refunded to recorded payer)
+    #[account(
+        mut,
+        close = rent_payer,
+        constraint = trade_record.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+        constraint = trade_record.settled @ TradingError::TradeNotSettled,
+        constraint = trade_record.rent_payer == rent_payer.key() @ TradingError::InvalidRentPayer,
+    )]
+    pub trade_record: Box<Account<'info, TradeRecord>>,
+    
+    /// Trade configuration PDA for retention delay
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, TradeConfig>>,
+    
+    /// Original rent payer (relayer that created the record)
+    /// CHECK: Validated against trade_record.rent_payer, only receives lamports
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+    
+    /// Anyone can crank the close
+    pub closer: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseTradeRecord>) -> Result<()> {
+    let trade_record = &ctx.accounts.trade_record;
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // Validate retention delay has elapsed
+    require!(
+        trade_record.can_close(
+            ctx.accounts.config.technical_config.trade_record_retention,
+            current_time,
+        ),
+        TradingError::RetentionPeriodActive
+    );
+    
+    let rent_refunded = trade_record.to_account_info().lamports();
+    
+    emit!(TradeRecordArchived {
+        trade_id: trade_record.trade_id,
+        token_id: trade_record.token_id,
+        buyer: trade_record.buyer,
+        seller: trade_record.seller,
+        collateral_mint: trade_record.collateral_mint,
+        filled_amount: trade_record.filled_amount,
+        price: trade_record.price,
+        buyer_collateral: trade_record.buyer_collateral,
+        seller_collateral: trade_record.seller_collateral,
+        match_time: trade_record.match_time,
+        settlement_deadline: trade_record.settlement_deadline,
+        buyer_fee: trade_record.buyer_fee,
+        seller_fee: trade_record.seller_fee,
+        settled_amount: trade_record.settled_amount,
+        settled_at: trade_record.settled_at,
+        rent_payer: trade_record.rent_payer,
+        rent_refunded,
+        closer: ctx.accounts.closer.key(),
+        closed_at: current_time,
+    });
+    
+    msg!(
+        "Trade record closed: trade_id: {} - rent_payer: {} - rent_refunded: {}",
+        trade_record.trade_id,
+        trade_record.rent_payer,
+        rent_refunded
+    );
+    
+    Ok(())
+}
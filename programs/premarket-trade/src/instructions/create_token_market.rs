@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::TradingError;
 use crate::events::TokenMarketCreated;
+use crate::utils::validate_collateral_mint_configs;
 
 #[derive(Accounts)]
 #[instruction(symbol: String, name: String, settle_time_limit: u32)]
@@ -35,11 +36,13 @@ pub struct CreateTokenMarket<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Remaining accounts: vault CollateralMintConfig PDA for each accepted collateral mint (same order)
 pub fn handler(
     ctx: Context<CreateTokenMarket>,
     symbol: String,
     name: String,
     settle_time_limit: u32,
+    accepted_collateral: Vec<Pubkey>,
 ) -> Result<()> {
     // Get the account key before mutable borrow
     let token_market_key = ctx.accounts.token_market.key();
@@ -49,6 +52,14 @@ pub fn handler(
     require!(symbol.trim().len() > 0, TradingError::SymbolTooLong);
     require!(name.trim().len() > 0, TradingError::NameTooLong);
     
+    // Accepted collateral must be allowlisted and enabled in the vault
+    TokenMarket::validate_accepted_collateral(&accepted_collateral)?;
+    validate_collateral_mint_configs(
+        &accepted_collateral,
+        ctx.remaining_accounts,
+        &ctx.accounts.config.vault_program,
+    )?;
+    
     // Set token_id to account address for EVM compatibility
     token_market.token_id = token_market_key;
     token_market.symbol = symbol.clone();
@@ -57,6 +68,17 @@ pub fn handler(
     token_market.mapping_time = None;
     token_market.settle_time_limit = settle_time_limit;
     token_market.created_at = Clock::get()?.unix_timestamp;
+    token_market.require_order_signatures = true;
+    token_market.accepted_collateral = accepted_collateral.clone();
+    token_market.version = crate::common::CURRENT_ACCOUNT_VERSION;
+    token_market.economic_override = None;
+    token_market.status = crate::common::MarketStatus::Listed;
+    token_market.real_mint_decimals = 0;
+    token_market.conversion_numerator = 0;
+    token_market.conversion_denominator = 0;
+    token_market.settled_trade_count = 0;
+    token_market.settlement_started_at = 0;
+    token_market.reserved = [0; 69];
     
     // Emit event with correct structure according to spec
     emit!(TokenMarketCreated {
@@ -65,6 +87,7 @@ pub fn handler(
         name: token_market.name.clone(),
         settle_time_limit,
         created_at: token_market.created_at,
+        accepted_collateral,
     });
     
     msg!(
@@ -22,14 +22,20 @@
  * - Order matching (match_orders)
  * - Trade settlement (settle_trade)
  * - Trade cancellation (cancel_trade)
+ * - Voided trade refunds (refund_voided_trade)
  * - Order cancellation (cancel_order)
- * - Configuration updates (update_config)
+ * - Configuration changes (schedule_* / execute_config_change / update_treasury)
  * - Token market creation (create_token_market)
- * - Token mapping (map_token)
- * - Relayer management (manage_relayers)
+ * - Token mapping (map_token / schedule_token_remap / execute_token_remap)
+ * - Market economic overrides (schedule_market_economic_config / execute_market_config_change)
+ * - Relayer management (add_relayer / update_relayer)
  * 
  * ## ✅ Allowed During Pause
  * - Emergency unpause (this instruction)
+ * - Relayer revocation (remove_relayer)
+ * - Queued config change cancellation (cancel_config_change / cancel_market_config_change)
+ * - Queued token remap cancellation (cancel_token_remap)
+ * - Market status transitions (open_trading / halt_trading / enter_settlement / close_market / void_market)
  * - Read-only operations
  * 
  * ## 📈 Event Emission
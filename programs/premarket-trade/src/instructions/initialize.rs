@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::common::{EconomicConfig, TechnicalConfig};
+use crate::error::TradingError;
+use crate::events::TradingInitialized;
 
 #[derive(Accounts)]
 pub struct InitializeTrading<'info> {
@@ -22,19 +24,30 @@ pub struct InitializeTrading<'info> {
 pub fn handler(
     ctx: Context<InitializeTrading>,
     vault_program: Pubkey,
+    treasury: Pubkey,
     economic_config: EconomicConfig,
     technical_config: TechnicalConfig,
 ) -> Result<()> {
+    require!(treasury != Pubkey::default(), TradingError::InvalidTreasury);
+    
     let trade_config = &mut ctx.accounts.trade_config;
     
     trade_config.initialize(
         ctx.accounts.admin.key(),
         vault_program,
+        treasury,
         economic_config,
         technical_config,
         ctx.bumps.trade_config,
     );
     
-    msg!("Trading system initialized");
+    emit!(TradingInitialized {
+        admin: ctx.accounts.admin.key(),
+        vault_program,
+        fee_recipient: treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Trading system initialized - treasury: {}", treasury);
     Ok(())
 } 
\ No newline at end of file
@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::common::RelayerParams;
 use crate::error::TradingError;
-use crate::events::{RelayerAdded, RelayerRemoved};
+use crate::events::{RelayerAdded, RelayerRemoved, RelayerUpdated};
 
 #[derive(Accounts)]
-pub struct ManageRelayers<'info> {
-    /// Trade configuration PDA to manage relayers
+#[instruction(relayer: Pubkey)]
+pub struct AddRelayer<'info> {
+    /// Trade configuration PDA (admin validation + relayer count)
     #[account(
         mut,
         seeds = [TradeConfig::TRADE_CONFIG_SEED],
@@ -14,80 +16,234 @@ pub struct ManageRelayers<'info> {
         constraint = !config.paused @ TradingError::TradingPaused,
     )]
     pub config: Account<'info, TradeConfig>,
-    
+
+    /// RelayerInfo PDA (init fails if the relayer is already registered)
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RelayerInfo::INIT_SPACE,
+        seeds = [RelayerInfo::RELAYER_INFO_SEED, relayer.as_ref()],
+        bump,
+    )]
+    pub relayer_info: Account<'info, RelayerInfo>,
+
     /// Admin signer (must match config.admin)
     #[account(mut)]
     pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<ManageRelayers>,
+/// One-off move of a relayer from the legacy `TradeConfig.relayers` list to a RelayerInfo PDA
+/// Legacy entries are not consulted by match_orders - run once per entry after migrate_trade_config
+#[derive(Accounts)]
+#[instruction(relayer: Pubkey)]
+pub struct MigrateLegacyRelayer<'info> {
+    /// Trade configuration PDA (admin validation + legacy list + relayer count)
+    #[account(
+        mut,
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// RelayerInfo PDA (init fails if the relayer is already registered)
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RelayerInfo::INIT_SPACE,
+        seeds = [RelayerInfo::RELAYER_INFO_SEED, relayer.as_ref()],
+        bump,
+    )]
+    pub relayer_info: Account<'info, RelayerInfo>,
+
+    /// Admin signer (must match config.admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRelayer<'info> {
+    /// Trade configuration PDA for admin validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// RelayerInfo PDA to update
+    #[account(
+        mut,
+        seeds = [RelayerInfo::RELAYER_INFO_SEED, relayer_info.relayer.as_ref()],
+        bump = relayer_info.bump,
+    )]
+    pub relayer_info: Account<'info, RelayerInfo>,
+
+    /// Admin signer (must match config.admin)
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveRelayer<'info> {
+    /// Trade configuration PDA (admin validation + relayer count)
+    /// Not gated on pause so a compromised relayer can be revoked during an emergency
+    #[account(
+        mut,
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// RelayerInfo PDA to close (rent refunded to admin)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [RelayerInfo::RELAYER_INFO_SEED, relayer_info.relayer.as_ref()],
+        bump = relayer_info.bump,
+    )]
+    pub relayer_info: Account<'info, RelayerInfo>,
+
+    /// Admin signer (must match config.admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn add_relayer_handler(
+    ctx: Context<AddRelayer>,
     relayer: Pubkey,
-    add: bool,
+    params: RelayerParams,
 ) -> Result<()> {
-    let config = &mut ctx.accounts.config;
     let admin = ctx.accounts.admin.key();
     let timestamp = Clock::get()?.unix_timestamp;
-    
+
     // Validate relayer address is not zero
     require!(relayer != Pubkey::default(), TradingError::InvalidTokenAddress);
-    
-    if add {
-        // Add relayer logic
-        require!(
-            !config.is_relayer(&relayer),
-            TradingError::TooManyRelayers  // Reuse error for "already exists"
-        );
-        
-        // Check max relayers limit (10 according to business requirements)
-        require!(
-            config.relayers.len() < 10,
-            TradingError::TooManyRelayers
-        );
-        
-        // Add relayer to list
-        config.add_relayer(relayer)?;
-        
-        // Emit RelayerAdded event
-        emit!(RelayerAdded {
-            admin,
-            relayer,
-            total_relayers: config.relayers.len() as u8,
-            timestamp,
-        });
-        
-        msg!(
-            "Relayer added: {} by admin: {} - Total relayers: {}",
-            relayer,
-            admin,
-            config.relayers.len()
-        );
-        
-    } else {
-        // Remove relayer logic
-        require!(
-            config.is_relayer(&relayer),
-            TradingError::UnauthorizedRelayer  // Relayer doesn't exist
-        );
-        
-        // Remove relayer from list
-        config.remove_relayer(relayer)?;
-        
-        // Emit RelayerRemoved event
-        emit!(RelayerRemoved {
-            admin,
-            relayer,
-            total_relayers: config.relayers.len() as u8,
-            timestamp,
-        });
-        
-        msg!(
-            "Relayer removed: {} by admin: {} - Total relayers: {}",
-            relayer,
-            admin,
-            config.relayers.len()
-        );
-    }
-    
+
+    let relayer_info = &mut ctx.accounts.relayer_info;
+    relayer_info.initialize(relayer, &params, admin, timestamp, ctx.bumps.relayer_info)?;
+
+    let config = &mut ctx.accounts.config;
+    config.increment_relayer_count()?;
+
+    emit!(RelayerAdded {
+        admin,
+        relayer,
+        permissions: relayer_info.permissions,
+        market: relayer_info.market,
+        active_from: relayer_info.active_from,
+        expires_at: relayer_info.expires_at,
+        total_relayers: config.relayer_count,
+        timestamp,
+    });
+
+    msg!(
+        "Relayer added: {} by admin: {} - permissions: {:#04b} - Total relayers: {}",
+        relayer,
+        admin,
+        relayer_info.permissions,
+        config.relayer_count
+    );
+
+    Ok(())
+}
+
+/// Legacy relayers keep their old authority: match on every market, no expiry
+pub fn migrate_legacy_relayer_handler(
+    ctx: Context<MigrateLegacyRelayer>,
+    relayer: Pubkey,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let config = &mut ctx.accounts.config;
+    config.take_legacy_relayer(&relayer)?;
+
+    let params = RelayerParams {
+        permissions: RelayerInfo::PERMISSION_MATCH,
+        market: None,
+        active_from: timestamp,
+        expires_at: 0,
+    };
+    let relayer_info = &mut ctx.accounts.relayer_info;
+    relayer_info.initialize(relayer, &params, admin, timestamp, ctx.bumps.relayer_info)?;
+    config.increment_relayer_count()?;
+
+    emit!(RelayerAdded {
+        admin,
+        relayer,
+        permissions: relayer_info.permissions,
+        market: relayer_info.market,
+        active_from: relayer_info.active_from,
+        expires_at: relayer_info.expires_at,
+        total_relayers: config.relayer_count,
+        timestamp,
+    });
+
+    msg!(
+        "Legacy relayer migrated: {} - remaining legacy entries: {} - Total relayers: {}",
+        relayer,
+        config.relayers.len(),
+        config.relayer_count
+    );
+
+    Ok(())
+}
+
+pub fn update_relayer_handler(
+    ctx: Context<UpdateRelayer>,
+    params: RelayerParams,
+) -> Result<()> {
+    let relayer_info = &mut ctx.accounts.relayer_info;
+    relayer_info.update(&params)?;
+
+    emit!(RelayerUpdated {
+        admin: ctx.accounts.admin.key(),
+        relayer: relayer_info.relayer,
+        permissions: relayer_info.permissions,
+        market: relayer_info.market,
+        active_from: relayer_info.active_from,
+        expires_at: relayer_info.expires_at,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Relayer updated: {} - permissions: {:#04b}",
+        relayer_info.relayer,
+        relayer_info.permissions
+    );
+
+    Ok(())
+}
+
+pub fn remove_relayer_handler(ctx: Context<RemoveRelayer>) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    let relayer_info = &ctx.accounts.relayer_info;
+
+    let config = &mut ctx.accounts.config;
+    config.decrement_relayer_count();
+
+    emit!(RelayerRemoved {
+        admin,
+        relayer: relayer_info.relayer,
+        matches_count: relayer_info.matches_count,
+        matched_amount: relayer_info.matched_amount,
+        total_relayers: config.relayer_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Relayer removed: {} by admin: {} - Total relayers: {}",
+        relayer_info.relayer,
+        admin,
+        config.relayer_count
+    );
+
     Ok(())
-} 
\ No newline at end of file
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 use crate::state::*;
 use crate::error::TradingError;
 use crate::events::TokenMapped;
@@ -11,14 +11,13 @@ pub struct MapToken<'info> {
         mut,
         constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
         constraint = token_market.real_mint.is_none() @ TradingError::TokenAlreadyMapped,
+        constraint = !token_market.status.is_final() @ TradingError::InvalidMarketStatus,
     )]
     pub token_market: Account<'info, TokenMarket>,
     
-    /// Real token mint to map to this market
-    #[account(
-        constraint = real_mint.to_account_info().owner == &anchor_spl::token::ID @ TradingError::InvalidTokenMint,
-    )]
-    pub real_mint: Account<'info, Mint>,
+    /// Real token mint to map to this market (SPL Token or Token-2022)
+    /// Owner is checked by InterfaceAccount, Token-2022 extensions in handler
+    pub real_mint: Box<InterfaceAccount<'info, Mint>>,
     
     /// Trade configuration PDA for admin validation
     #[account(
@@ -37,6 +36,8 @@ pub struct MapToken<'info> {
 pub fn handler(
     ctx: Context<MapToken>,
     real_mint: Pubkey,
+    conversion_numerator: u64,
+    conversion_denominator: u64,
 ) -> Result<()> {
     // Get token market key before mutable borrow
     let token_market_key = ctx.accounts.token_market.key();
@@ -48,28 +49,47 @@ pub fn handler(
         TradingError::InvalidTokenMint
     );
     
+    // Reject Token-2022 mints with extensions settlement cannot handle
+    require!(
+        escrow_vault::utils::is_supported_mint(&ctx.accounts.real_mint.to_account_info())?,
+        TradingError::UnsupportedMintExtension
+    );
+    
     // Additional validation: ensure token market exists and is valid
     require!(
         token_market.token_id == token_market_key,
         TradingError::InvalidTokenAddress
     );
     
-    // Map the real token to this market
+    // Map the real token to this market, snapshot decimals and conversion ratio
     let mapping_time = Clock::get()?.unix_timestamp;
-    token_market.real_mint = Some(real_mint);
-    token_market.mapping_time = Some(mapping_time);
+    let real_mint_decimals = ctx.accounts.real_mint.decimals;
+    token_market.map_token(
+        real_mint,
+        real_mint_decimals,
+        conversion_numerator,
+        conversion_denominator,
+        mapping_time,
+    )?;
     
     // Emit TokenMapped event
     emit!(TokenMapped {
         token_id: token_market.token_id,
         real_mint,
         mapping_time,
+        real_mint_decimals,
+        conversion_numerator,
+        conversion_denominator,
+        settle_time_limit: token_market.settle_time_limit,
     });
     
     msg!(
-        "Token mapped successfully: market_id: {} -> real_mint: {} at time: {}",
+        "Token mapped successfully: market_id: {} -> real_mint: {} (decimals: {}, ratio: {}/{}) at time: {}",
         token_market.token_id,
         real_mint,
+        real_mint_decimals,
+        conversion_numerator,
+        conversion_denominator,
         mapping_time
     );
     
@@ -0,0 +1,251 @@
+/*!
+ * # MARKET ECONOMIC CONFIG INSTRUCTIONS
+ *
+ * ## 🎯 Business Purpose
+ * Sets or clears a market-level EconomicConfig override (collateral ratios, fees,
+ * reward and penalty rates) resolved before the TradeConfig defaults.
+ *
+ * ## ⏳ Timelocked Changes
+ * Overrides go through the same queue as global config changes:
+ * 1. `schedule_market_economic_config`: admin queues a validated override effective >= MIN_CONFIG_CHANGE_DELAY out
+ * 2. `execute_market_config_change`: anyone applies it once `effective_at` has passed
+ * 3. `cancel_market_config_change`: admin discards it (also allowed while paused)
+ *
+ * Reward and penalty rates are snapshotted on the TradeRecord at match, so an
+ * executed override only affects trades matched afterwards.
+ *
+ * ## 🛡️ Security Requirements
+ * - Only admin can schedule / cancel an override
+ * - One queued override per market (QueuedConfigChange seeded with the market)
+ * - Override re-validated on execution
+ *
+ * ## 📈 Event Emission
+ * Emits `PendingMarketConfigChange`, `PendingMarketConfigChangeCancelled` and
+ * `MarketEconomicConfigUpdated`
+ */
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::TradingError;
+use crate::events::{
+    MarketEconomicConfigUpdated, PendingMarketConfigChange, PendingMarketConfigChangeCancelled,
+};
+use crate::common::{ConfigChange, EconomicConfig};
+
+#[derive(Accounts)]
+pub struct ScheduleMarketEconomicConfig<'info> {
+    /// TokenMarket the override applies to
+    #[account(
+        constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+    )]
+    pub token_market: Account<'info, TokenMarket>,
+
+    /// Queued change PDA (init fails while another override is queued for the market)
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + QueuedConfigChange::INIT_SPACE,
+        seeds = [
+            QueuedConfigChange::QUEUED_CONFIG_CHANGE_SEED,
+            &[ConfigChange::MARKET_ECONOMIC],
+            token_market.key().as_ref()
+        ],
+        bump,
+    )]
+    pub queued_change: Account<'info, QueuedConfigChange>,
+
+    /// Trade configuration PDA for admin validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Admin signer (must match config.admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteMarketConfigChange<'info> {
+    /// TokenMarket to update
+    #[account(
+        mut,
+        constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+    )]
+    pub token_market: Account<'info, TokenMarket>,
+
+    /// Queued change PDA (closed on execution)
+    #[account(
+        mut,
+        close = rent_receiver,
+        seeds = [
+            QueuedConfigChange::QUEUED_CONFIG_CHANGE_SEED,
+            &[ConfigChange::MARKET_ECONOMIC],
+            token_market.key().as_ref()
+        ],
+        bump = queued_change.bump,
+    )]
+    pub queued_change: Account<'info, QueuedConfigChange>,
+
+    /// Trade configuration PDA for pause validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// CHECK: Admin that scheduled the change - receives the rent back
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == queued_change.scheduled_by @ TradingError::InvalidRentPayer,
+    )]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// Anyone can execute once effective
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelMarketConfigChange<'info> {
+    /// TokenMarket the queued override applies to
+    #[account(
+        constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+    )]
+    pub token_market: Account<'info, TokenMarket>,
+
+    /// Queued change PDA (closed, rent refunded to admin)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            QueuedConfigChange::QUEUED_CONFIG_CHANGE_SEED,
+            &[ConfigChange::MARKET_ECONOMIC],
+            token_market.key().as_ref()
+        ],
+        bump = queued_change.bump,
+    )]
+    pub queued_change: Account<'info, QueuedConfigChange>,
+
+    /// Trade configuration PDA for admin validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Admin signer (must match config.admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Queue a market override - `economic_override = None` clears it (market falls back to TradeConfig)
+pub fn schedule_market_config_handler(
+    ctx: Context<ScheduleMarketEconomicConfig>,
+    economic_override: Option<EconomicConfig>,
+    effective_at: i64,
+) -> Result<()> {
+    let token_id = ctx.accounts.token_market.token_id;
+    let admin = ctx.accounts.admin.key();
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Step 1: Validate new override
+    if let Some(config) = &economic_override {
+        config.validate()?;
+    }
+
+    // Step 2: Queue change
+    ctx.accounts.queued_change.initialize(
+        ConfigChange::MarketEconomic(economic_override.clone()),
+        admin,
+        effective_at,
+        current_time,
+        ctx.bumps.queued_change,
+    )?;
+
+    emit!(PendingMarketConfigChange {
+        token_id,
+        admin,
+        new_override: economic_override,
+        scheduled_at: current_time,
+        effective_at,
+    });
+
+    msg!(
+        "Market economic config scheduled: token_id: {} - effective at: {}",
+        token_id,
+        effective_at
+    );
+
+    Ok(())
+}
+
+/// Apply a queued market override once its delay has elapsed
+pub fn execute_market_config_handler(ctx: Context<ExecuteMarketConfigChange>) -> Result<()> {
+    let queued_change = &ctx.accounts.queued_change;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Step 1: Check timelock
+    require!(
+        queued_change.is_ready(current_time),
+        TradingError::ConfigChangeNotReady
+    );
+
+    let ConfigChange::MarketEconomic(economic_override) = queued_change.change.clone() else {
+        return err!(TradingError::InvalidConfigChangeKind);
+    };
+
+    // Step 2: Re-validate and apply
+    let token_market = &mut ctx.accounts.token_market;
+    let old_override = token_market.economic_override.clone();
+    token_market.set_economic_override(economic_override.clone())?;
+
+    emit!(MarketEconomicConfigUpdated {
+        token_id: token_market.token_id,
+        admin: queued_change.scheduled_by,
+        executed_by: ctx.accounts.executor.key(),
+        old_override,
+        new_override: economic_override,
+        timestamp: current_time,
+    });
+
+    msg!(
+        "Market economic config updated: token_id: {} - override: {}",
+        token_market.token_id,
+        token_market.economic_override.is_some()
+    );
+
+    Ok(())
+}
+
+/// Discard a queued market override
+pub fn cancel_market_config_handler(ctx: Context<CancelMarketConfigChange>) -> Result<()> {
+    let queued_change = &ctx.accounts.queued_change;
+    let token_id = ctx.accounts.token_market.token_id;
+
+    let ConfigChange::MarketEconomic(economic_override) = queued_change.change.clone() else {
+        return err!(TradingError::InvalidConfigChangeKind);
+    };
+
+    emit!(PendingMarketConfigChangeCancelled {
+        token_id,
+        admin: ctx.accounts.admin.key(),
+        new_override: economic_override,
+        effective_at: queued_change.effective_at,
+        cancelled_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Market economic config change cancelled: token_id: {} - admin: {}",
+        token_id,
+        ctx.accounts.admin.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+/*!
+ * # MARKET STATUS INSTRUCTIONS
+ *
+ * ## 🎯 Business Purpose
+ * Drives the TokenMarket lifecycle so trading, settlement and cancellation only
+ * happen in the phase of the launch they belong to.
+ *
+ * ## 🔄 Lifecycle
+ * ```text
+ * Listed ⇄ Trading → Settlement → Closed
+ *    └───────┴───────────┴──────→ Voided
+ * ```
+ * - `open_trading`: Listed → Trading
+ * - `halt_trading`: Trading → Listed
+ * - `enter_settlement`: Listed / Trading → Settlement (token must be mapped)
+ * - `close_market`: Settlement → Closed, only once the last settlement window is over
+ *   (`now > max(settlement_started_at, mapping_time) + settle_time_limit`)
+ * - `void_market`: Listed / Trading / Settlement → Voided
+ *
+ * ## 🚦 Instruction Gating
+ * | Status     | match_orders | settle_trade | cancel_trade | cancel_order |
+ * |------------|--------------|--------------|--------------|--------------|
+ * | Listed     | ❌           | ❌           | ❌           | ✅           |
+ * | Trading    | ✅           | ❌           | ❌           | ✅           |
+ * | Settlement | ❌           | ✅           | ✅           | ✅           |
+ * | Closed     | ❌           | ❌           | ✅           | ❌           |
+ * | Voided     | ❌           | ❌           | ❌           | ❌           |
+ *
+ * Trades of a Voided market are unwound without penalty by the permissionless
+ * `refund_voided_trade` crank.
+ *
+ * ## 🛡️ Security Requirements
+ * - Only admin can change market status
+ * - Not pause-gated so markets can be halted / voided during an emergency
+ *
+ * ## 📈 Event Emission
+ * Emits `MarketStatusChanged` on every transition for frontends and indexers
+ */
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::TradingError;
+use crate::events::MarketStatusChanged;
+use crate::common::MarketStatus;
+
+#[derive(Accounts)]
+pub struct MarketStatusControl<'info> {
+    /// TokenMarket account to transition
+    #[account(
+        mut,
+        constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+    )]
+    pub token_market: Account<'info, TokenMarket>,
+
+    /// Trade configuration PDA for admin validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Admin signer (must match config.admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Allow order matching
+pub fn open_trading_handler(ctx: Context<MarketStatusControl>) -> Result<()> {
+    transition(ctx, MarketStatus::Trading)
+}
+
+/// Stop order matching (back to Listed)
+pub fn halt_trading_handler(ctx: Context<MarketStatusControl>) -> Result<()> {
+    transition(ctx, MarketStatus::Listed)
+}
+
+/// Stop matching and open settlement of mapped token
+pub fn enter_settlement_handler(ctx: Context<MarketStatusControl>) -> Result<()> {
+    transition(ctx, MarketStatus::Settlement)
+}
+
+/// End settlement once no trade can settle anymore (leftover trades remain cancellable by buyers)
+pub fn close_market_handler(ctx: Context<MarketStatusControl>) -> Result<()> {
+    transition(ctx, MarketStatus::Closed)
+}
+
+/// Void market after a cancelled launch
+pub fn void_market_handler(ctx: Context<MarketStatusControl>) -> Result<()> {
+    transition(ctx, MarketStatus::Voided)
+}
+
+fn transition(ctx: Context<MarketStatusControl>, next: MarketStatus) -> Result<()> {
+    let token_market = &mut ctx.accounts.token_market;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let previous = token_market.transition(next, timestamp)?;
+
+    emit!(MarketStatusChanged {
+        token_id: token_market.token_id,
+        previous_status: previous,
+        new_status: next,
+        admin: ctx.accounts.admin.key(),
+        timestamp,
+    });
+
+    msg!(
+        "Market status changed: token_id: {} - {:?} -> {:?}",
+        token_market.token_id,
+        previous,
+        next
+    );
+
+    Ok(())
+}
@@ -1,41 +1,111 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
-use crate::common::PreOrder;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use crate::common::{PreOrder, safe_calculate_fee, CURRENT_ACCOUNT_VERSION, VAULT_SIGNER_SEED};
 use crate::state::*;
 use crate::error::TradingError;
 use crate::events::OrdersMatched;
-use crate::utils::{can_match_orders, calculate_fill_amount, validate_order_business_logic};
+use crate::utils::{
+    can_match_orders, calculate_fill_amount, calculate_order_hash, generate_trade_id,
+    validate_order_business_logic, verify_order_signature,
+};
 
 // Import vault program for actual CPI calls
 use escrow_vault::cpi;
 use escrow_vault::program::EscrowVault;
 
 #[derive(Accounts)]
+#[instruction(buy_order: PreOrder, sell_order: PreOrder)]
 pub struct MatchOrders<'info> {
-    /// TradeRecord account (User-controlled keypair, not PDA)
-    /// Client generates keypair, Anchor handles account creation/initialization
+    /// OrderStatus PDA tracking buy order fills (created on first match)
     #[account(
         init_if_needed,
         payer = relayer,
+        space = 8 + OrderStatus::INIT_SPACE,
+        seeds = [
+            OrderStatus::ORDER_STATUS_SEED,
+            &calculate_order_hash(&buy_order)
+        ],
+        bump,
+    )]
+    pub buy_order_status: Box<Account<'info, OrderStatus>>,
+    
+    /// OrderStatus PDA tracking sell order fills (created on first match)
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + OrderStatus::INIT_SPACE,
+        seeds = [
+            OrderStatus::ORDER_STATUS_SEED,
+            &calculate_order_hash(&sell_order)
+        ],
+        bump,
+    )]
+    pub sell_order_status: Box<Account<'info, OrderStatus>>,
+    
+    /// TradeRecord PDA derived from both order hashes and the buy order's fill offset
+    /// (`filled_quantity` before this fill), so every partial fill of a pair gets its own record
+    /// `init` rejects an existing record, so live trades can never be overwritten
+    #[account(
+        init,
+        payer = relayer,
         space = 8 + TradeRecord::INIT_SPACE,
+        seeds = [
+            TradeRecord::TRADE_RECORD_SEED,
+            &generate_trade_id(
+                &calculate_order_hash(&buy_order),
+                &calculate_order_hash(&sell_order)
+            ),
+            &buy_order_status.filled_quantity.to_le_bytes()
+        ],
+        bump,
     )]
     pub trade_record: Box<Account<'info, TradeRecord>>,
     
+    /// Buyer NonceState PDA (may be uninitialized = nothing invalidated)
+    /// CHECK: Address validated by seeds, contents read via NonceState::load_min_nonce
+    #[account(
+        seeds = [
+            NonceState::NONCE_STATE_SEED,
+            buy_order.trader.as_ref()
+        ],
+        bump,
+    )]
+    pub buyer_nonce_state: UncheckedAccount<'info>,
+    
+    /// Seller NonceState PDA (may be uninitialized = nothing invalidated)
+    /// CHECK: Address validated by seeds, contents read via NonceState::load_min_nonce
+    #[account(
+        seeds = [
+            NonceState::NONCE_STATE_SEED,
+            sell_order.trader.as_ref()
+        ],
+        bump,
+    )]
+    pub seller_nonce_state: UncheckedAccount<'info>,
+    
     /// TokenMarket for the trading pair
     #[account(
         constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+        constraint = token_market.is_trading() @ TradingError::TradingNotActive,
     )]
     pub token_market: Box<Account<'info, TokenMarket>>,
     
-    /// Trade configuration PDA for relayer validation
+    /// Trade configuration PDA
     #[account(
         seeds = [TradeConfig::TRADE_CONFIG_SEED],
         bump = config.bump,
-        constraint = config.is_relayer(&relayer.key()) @ TradingError::UnauthorizedRelayer,
         constraint = !config.paused @ TradingError::TradingPaused,
     )]
     pub config: Box<Account<'info, TradeConfig>>,
     
+    /// RelayerInfo PDA of the signing relayer (permission, scope and window checked in handler)
+    #[account(
+        mut,
+        seeds = [RelayerInfo::RELAYER_INFO_SEED, relayer.key().as_ref()],
+        bump = relayer_info.bump,
+    )]
+    pub relayer_info: Box<Account<'info, RelayerInfo>>,
+    
     /// Authorized relayer executing the match
     #[account(mut)]
     pub relayer: Signer<'info>,
@@ -55,16 +125,67 @@ pub struct MatchOrders<'info> {
     )]
     pub vault_config: Box<Account<'info, escrow_vault::state::VaultConfig>>,
     
-    /// Buyer balance PDA - validated in handler
-    /// CHECK: Buyer balance account validated via CPI to vault program
-    #[account(mut)]
+    /// Vault AuthorizedTrader PDA of this program (CPI authorization)
+    #[account(
+        seeds = [
+            escrow_vault::state::AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            crate::ID.as_ref()
+        ],
+        bump = authorized_trader.bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub authorized_trader: Box<Account<'info, escrow_vault::state::AuthorizedTrader>>,
+    
+    /// Program signer PDA - signs vault CPIs (matched against AuthorizedTrader.signer)
+    /// CHECK: PDA of this program, holds no data
+    #[account(
+        seeds = [VAULT_SIGNER_SEED],
+        bump,
+    )]
+    pub vault_signer: UncheckedAccount<'info>,
+    
+    /// Buyer balance PDA of the buy order's trader and collateral mint
+    /// CHECK: Seeds pin the account to buy_order.trader, contents validated via CPI
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::UserBalance::USER_BALANCE_SEED,
+            buy_order.trader.as_ref(),
+            buy_order.collateral_token.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
     pub buyer_balance: AccountInfo<'info>,
     
-    /// Seller balance PDA - validated in handler  
-    /// CHECK: Seller balance account validated via CPI to vault program
-    #[account(mut)]
+    /// Seller balance PDA of the sell order's trader and collateral mint
+    /// CHECK: Seeds pin the account to sell_order.trader, contents validated via CPI
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::UserBalance::USER_BALANCE_SEED,
+            sell_order.trader.as_ref(),
+            sell_order.collateral_token.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
     pub seller_balance: AccountInfo<'info>,
     
+    /// Treasury balance PDA - receives buyer fee
+    /// CHECK: Seeds pin the account to config.treasury, contents validated via CPI
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::UserBalance::USER_BALANCE_SEED,
+            config.treasury.as_ref(),
+            buy_order.collateral_token.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub treasury_balance: AccountInfo<'info>,
+    
     /// Vault authority PDA - properly typed and validated
     #[account(
         seeds = [
@@ -76,18 +197,30 @@ pub struct MatchOrders<'info> {
     )]
     pub vault_authority: Box<Account<'info, escrow_vault::state::VaultAuthority>>,
     
+    /// Collateral allowlist entry - mint must be enabled in the vault
+    #[account(
+        seeds = [
+            escrow_vault::state::CollateralMintConfig::COLLATERAL_MINT_CONFIG_SEED,
+            buyer_collateral_ata.mint.as_ref()
+        ],
+        bump = collateral_mint_config.bump,
+        seeds::program = vault_program.key(),
+        constraint = collateral_mint_config.enabled @ TradingError::CollateralMintNotAllowed,
+    )]
+    pub collateral_mint_config: Box<Account<'info, escrow_vault::state::CollateralMintConfig>>,
+    
     // Token accounts for collateral validation
     #[account(
         constraint = buyer_collateral_ata.mint == seller_collateral_ata.mint @ TradingError::TokenMintMismatch,
     )]
-    pub buyer_collateral_ata: Box<Account<'info, TokenAccount>>,
+    pub buyer_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    pub seller_collateral_ata: Box<Account<'info, TokenAccount>>,
+    pub seller_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     
-    /// 🛡️ INSTRUCTION SYSVAR - For precise CPI caller detection
+    /// 🛡️ INSTRUCTION SYSVAR - For precise CPI caller detection and order signature verification
     /// CHECK: Validated by constraint to ensure it's the instruction sysvar
     #[account(
         constraint = instruction_sysvar.key() == solana_program::sysvar::instructions::ID @ TradingError::InvalidInstructionSysvar
@@ -104,12 +237,15 @@ pub fn handler(
     // Get account keys before mutable borrows
     let trade_record_key = ctx.accounts.trade_record.key();
     let token_market_key = ctx.accounts.token_market.key();
-    
-    // Enhanced relayer authorization - ensure only authorized relayers can match
-    require!(
-        ctx.accounts.config.is_relayer(&ctx.accounts.relayer.key()),
-        TradingError::UnauthorizedRelayer
-    );
+    let buy_order_status_key = ctx.accounts.buy_order_status.key();
+    let sell_order_status_key = ctx.accounts.sell_order_status.key();
+    
+    // Relayer authorization - match permission, market scope and activity window
+    ctx.accounts.relayer_info.authorize(
+        RelayerInfo::PERMISSION_MATCH,
+        &token_market_key,
+        Clock::get()?.unix_timestamp,
+    )?;
     
     // Validate orders can be matched (business logic)
     can_match_orders(&buy_order, &sell_order)?;
@@ -128,6 +264,16 @@ pub fn handler(
     validate_order_business_logic(&buy_order, &buy_order.trader)?;
     validate_order_business_logic(&sell_order, &sell_order.trader)?;
     
+    // Reject orders invalidated by the traders' nonce floor
+    NonceState::validate_order_nonce(&ctx.accounts.buyer_nonce_state, buy_order.nonce)?;
+    NonceState::validate_order_nonce(&ctx.accounts.seller_nonce_state, sell_order.nonce)?;
+    
+    // Verify trader signatures unless the market runs in relayer-trust mode
+    if ctx.accounts.token_market.require_order_signatures {
+        verify_order_signature(&ctx.accounts.instruction_sysvar, &buy_order)?;
+        verify_order_signature(&ctx.accounts.instruction_sysvar, &sell_order)?;
+    }
+    
     // Additional business protections for relayer model
     let current_time = Clock::get()?.unix_timestamp;
     require!(
@@ -135,17 +281,43 @@ pub fn handler(
         TradingError::OrderExpired
     );
     
-    // Calculate actual fill amount
+    // Load fill state - rejects cancelled, filled and expired orders
+    load_order_status(
+        &mut ctx.accounts.buy_order_status,
+        buy_order_status_key,
+        &buy_order,
+        ctx.bumps.buy_order_status,
+        current_time,
+    )?;
+    load_order_status(
+        &mut ctx.accounts.sell_order_status,
+        sell_order_status_key,
+        &sell_order,
+        ctx.bumps.sell_order_status,
+        current_time,
+    )?;
+    
+    // Calculate actual fill amount (capped by what is left on each order)
     let actual_fill_amount = calculate_fill_amount(
-        buy_order.amount,
-        sell_order.amount,
+        ctx.accounts.buy_order_status.remaining_quantity(),
+        ctx.accounts.sell_order_status.remaining_quantity(),
         fill_amount,
     );
     
+    // Mapped market: fill must convert exactly to real token units
+    if ctx.accounts.token_market.is_mapped() {
+        ctx.accounts.token_market.to_real_amount(actual_fill_amount)?;
+    }
+    
+    // Resolve economic parameters (market override first)
+    let economic_config = ctx.accounts.token_market
+        .economic_config(&ctx.accounts.config.economic_config)
+        .clone();
+    
     // Validate fill amount
     require!(actual_fill_amount > 0, TradingError::ZeroAmount);
     require!(
-        actual_fill_amount >= ctx.accounts.config.economic_config.minimum_fill_amount,
+        actual_fill_amount >= economic_config.minimum_fill_amount,
         TradingError::BelowMinimumFill
     );
     
@@ -153,7 +325,19 @@ pub fn handler(
     let (buyer_collateral, seller_collateral) = calculate_collateral_requirements(
         actual_fill_amount,
         buy_order.price,
-        &ctx.accounts.config.economic_config,
+        &economic_config,
+    )?;
+    
+    // Calculate protocol fees - buyer pays at match, seller pays at settlement
+    let buyer_fee = safe_calculate_fee(
+        actual_fill_amount,
+        buy_order.price,
+        economic_config.buyer_fee_bps,
+    )?;
+    let seller_fee = safe_calculate_fee(
+        actual_fill_amount,
+        buy_order.price,
+        economic_config.seller_fee_bps,
     )?;
     
     // Validate collateral token
@@ -165,6 +349,10 @@ pub fn handler(
         sell_order.collateral_token == ctx.accounts.seller_collateral_ata.mint,
         TradingError::TokenMintMismatch
     );
+    require!(
+        ctx.accounts.token_market.accepts_collateral(&buy_order.collateral_token),
+        TradingError::CollateralMintNotAllowed
+    );
     
     // Lock buyer collateral via CPI to vault
     lock_buyer_collateral_cpi(&ctx, buyer_collateral)?;
@@ -172,17 +360,40 @@ pub fn handler(
     // Lock seller collateral via CPI to vault  
     lock_seller_collateral_cpi(&ctx, seller_collateral)?;
     
+    // Collect buyer fee into treasury vault balance
+    if buyer_fee > 0 {
+        collect_buyer_fee_cpi(&ctx, buy_order.trader, buyer_fee)?;
+    }
+    
+    // Record fills so the same orders cannot be replayed or overfilled
+    let buy_order_status = &mut ctx.accounts.buy_order_status;
+    let fill_offset = buy_order_status.filled_quantity; // TradeRecord PDA seed
+    buy_order_status.fill_order(actual_fill_amount)?;
+    buy_order_status.collateral_locked = buy_order_status.collateral_locked
+        .checked_add(buyer_collateral)
+        .ok_or(TradingError::MathOverflow)?;
+    
+    let sell_order_status = &mut ctx.accounts.sell_order_status;
+    sell_order_status.fill_order(actual_fill_amount)?;
+    sell_order_status.collateral_locked = sell_order_status.collateral_locked
+        .checked_add(seller_collateral)
+        .ok_or(TradingError::MathOverflow)?;
+    
     // 🔑 Calculate order hashes for tracking and audit trail
-    let buy_order_hash_bytes = crate::utils::calculate_order_hash(&buy_order);
-    let sell_order_hash_bytes = crate::utils::calculate_order_hash(&sell_order);
+    let buy_order_hash_bytes = calculate_order_hash(&buy_order);
+    let sell_order_hash_bytes = calculate_order_hash(&sell_order);
     
     // Convert to human-readable hex format
     let buy_order_hash = hex::encode(buy_order_hash_bytes);
     let sell_order_hash = hex::encode(sell_order_hash_bytes);
+    let trade_hash_bytes = generate_trade_id(&buy_order_hash_bytes, &sell_order_hash_bytes);
+    
+    // Track relayer activity
+    let match_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.relayer_info.record_match(actual_fill_amount, match_time)?;
     
     // Initialize TradeRecord
     let trade_record = &mut ctx.accounts.trade_record;
-    let match_time = Clock::get()?.unix_timestamp;
     
     trade_record.trade_id = trade_record_key;
     trade_record.buyer = buy_order.trader;
@@ -195,7 +406,22 @@ pub fn handler(
     trade_record.seller_collateral = seller_collateral;
     trade_record.match_time = match_time;
     trade_record.settled = false;
-    // trade_record.target_mint = None;
+    trade_record.buyer_fee = buyer_fee;
+    trade_record.seller_fee = seller_fee;
+    trade_record.settled_amount = 0;
+    trade_record.rent_payer = ctx.accounts.relayer.key();
+    trade_record.settled_at = 0;
+    trade_record.trade_hash = trade_hash_bytes;
+    trade_record.bump = ctx.bumps.trade_record;
+    trade_record.version = CURRENT_ACCOUNT_VERSION;
+    trade_record.settlement_deadline = ctx.accounts.token_market
+        .settlement_deadline(match_time)
+        .unwrap_or(0);
+    trade_record.legacy_collateral = false;
+    trade_record.fill_offset = fill_offset;
+    trade_record.seller_reward_bps = economic_config.seller_reward_bps;
+    trade_record.late_penalty_bps = economic_config.late_penalty_bps;
+    trade_record.reserved = [0; 11];
     
     // Emit enhanced OrdersMatched event with order hashes
     emit!(OrdersMatched {
@@ -208,10 +434,15 @@ pub fn handler(
         price: buy_order.price,
         buyer_collateral,
         seller_collateral,
+        buyer_fee,
+        seller_fee,
         match_time,
+        settlement_deadline: trade_record.settlement_deadline,
         // 🆕 Order hashes for tracking and audit
         buy_order_hash: buy_order_hash.clone(),
         sell_order_hash: sell_order_hash.clone(),
+        trade_hash: hex::encode(trade_hash_bytes),
+        fill_offset,
     });
     
     msg!(
@@ -229,6 +460,49 @@ pub fn handler(
     Ok(())
 }
 
+/// Initialize OrderStatus on first match and ensure the order can still be filled
+fn load_order_status(
+    order_status: &mut OrderStatus,
+    order_status_key: Pubkey,
+    order: &PreOrder,
+    bump: u8,
+    current_time: i64,
+) -> Result<()> {
+    if order_status.user == Pubkey::default() {
+        let order_type = if order.is_buy {
+            OrderType::Buy
+        } else {
+            OrderType::Sell
+        };
+        
+        order_status.initialize(
+            order_status_key,                 // order_id (PDA address)
+            order.token_id,                   // token_market
+            order.trader,                     // user
+            order_type,                       // order_type
+            order.amount,                     // quantity
+            0,                                // collateral_locked (accumulated per fill)
+            order.deadline,                   // expires_at
+            bump,                             // bump
+        );
+    }
+    
+    require!(
+        order_status.status != OrderStatusType::Cancelled,
+        TradingError::OrderAlreadyCancelled
+    );
+    require!(
+        order_status.status != OrderStatusType::Filled && order_status.remaining_quantity() > 0,
+        TradingError::OrderAlreadyFilled
+    );
+    require!(
+        order_status.status != OrderStatusType::Expired && !order_status.is_expired(current_time),
+        TradingError::OrderExpired
+    );
+    
+    Ok(())
+}
+
 /// Calculate collateral requirements based on economic config
 fn calculate_collateral_requirements(
     amount: u64,
@@ -265,18 +539,20 @@ fn lock_buyer_collateral_cpi(
     msg!("Locking buyer collateral via CPI: amount: {}", amount);
     
     // All accounts from same Context - unified 'ctx lifetime!
-    let cpi_accounts = cpi::accounts::SlashBalance {
+    let cpi_accounts = cpi::accounts::LockBalance {
         config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
         user_balance: ctx.accounts.buyer_balance.to_account_info(),
         vault_authority: ctx.accounts.vault_authority.to_account_info(),
-        instruction_sysvar: ctx.accounts.instruction_sysvar.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
     };
     
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
     let cpi_program = ctx.accounts.vault_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
     
     // Execute ACTUAL CPI call - NO LIFETIME CONFLICTS!
-    cpi::slash_balance(cpi_ctx, amount)?;
+    cpi::lock_balance(cpi_ctx, amount)?;
     
     msg!("Buyer collateral locked successfully via CPI: {}", amount);
     Ok(())
@@ -291,19 +567,49 @@ fn lock_seller_collateral_cpi(
     msg!("Locking seller collateral via CPI: amount: {}", amount);
     
     // All accounts from same Context - unified 'ctx lifetime!
-    let cpi_accounts = cpi::accounts::SlashBalance {
+    let cpi_accounts = cpi::accounts::LockBalance {
         config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
         user_balance: ctx.accounts.seller_balance.to_account_info(),
         vault_authority: ctx.accounts.vault_authority.to_account_info(),
-        instruction_sysvar: ctx.accounts.instruction_sysvar.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
     };
     
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
     let cpi_program = ctx.accounts.vault_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
     
     // Execute ACTUAL CPI call - NO LIFETIME CONFLICTS!
-    cpi::slash_balance(cpi_ctx, amount)?;
+    cpi::lock_balance(cpi_ctx, amount)?;
     
     msg!("Seller collateral locked successfully via CPI: {}", amount);
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Collect buyer protocol fee via CPI to vault program
+/// Moves vault balance from buyer to treasury (no token movement)
+fn collect_buyer_fee_cpi(
+    ctx: &Context<MatchOrders>,
+    buyer: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    msg!("Collecting buyer fee via CPI: amount: {}", amount);
+    
+    let cpi_accounts = cpi::accounts::TransferBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        from_balance: ctx.accounts.buyer_balance.to_account_info(),
+        to_balance: ctx.accounts.treasury_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::transfer_balance(cpi_ctx, buyer, ctx.accounts.config.treasury, amount)?;
+    
+    msg!("Buyer fee collected successfully via CPI: {}", amount);
+    Ok(())
+}
@@ -0,0 +1,441 @@
+use anchor_lang::prelude::*;
+use escrow_vault::migration::{realloc_account, realloc_and_load, store_account};
+use crate::state::*;
+use crate::error::TradingError;
+use crate::events::{AcceptedCollateralUpdated, AccountMigrated};
+use crate::utils::validate_collateral_mint_configs;
+
+/// Upgrade TradeConfig created with an older layout (Admin only)
+/// Deployed v0 accounts are decoded with `LegacyTradeConfig`; treasury starts as admin
+/// Freezes the v0 reward / penalty rates for migrate_trade_record
+/// Must run before migrate_token_market, which loads TradeConfig for admin validation
+#[derive(Accounts)]
+pub struct MigrateTradeConfig<'info> {
+    /// CHECK: Old layouts cannot be loaded as Account<TradeConfig> - owner,
+    /// discriminator, seeds and admin are validated in handler
+    #[account(
+        mut,
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// Admin signer (must match config.admin) - pays the rent for the additional bytes
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade TokenMarket created with an older layout (Admin only)
+/// Legacy markets come up with an empty `accepted_collateral` and cannot match until
+/// it is set - pass the mints here (or call set_accepted_collateral afterwards)
+/// Mapped legacy markets get an unknown settled trade count and can never be remapped
+#[derive(Accounts)]
+pub struct MigrateTokenMarket<'info> {
+    /// CHECK: Old layouts cannot be loaded as Account<TokenMarket> - owner,
+    /// discriminator and token_id are validated in handler
+    #[account(mut)]
+    pub token_market: UncheckedAccount<'info>,
+
+    /// Trade configuration PDA for admin validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Admin signer - pays the rent for the additional bytes
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade TradeRecord created with an older layout (Admin only)
+/// Accepts pre-upgrade keypair records (no trade_hash / bump) as well as PDA records
+/// Pre-upgrade records get the admin as rent payer and the rates frozen by migrate_trade_config
+#[derive(Accounts)]
+pub struct MigrateTradeRecord<'info> {
+    /// CHECK: Old layouts cannot be loaded as Account<TradeRecord> - owner,
+    /// discriminator and PDA seeds are validated in handler
+    #[account(mut)]
+    pub trade_record: UncheckedAccount<'info>,
+
+    /// Trade configuration PDA for admin validation - holds the frozen rates of
+    /// pre-upgrade trades (must be migrated first)
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Admin signer - pays the rent for the additional bytes
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: upgrade OrderStatus created with an older layout
+#[derive(Accounts)]
+pub struct MigrateOrderStatus<'info> {
+    /// CHECK: Old layouts cannot be loaded as Account<OrderStatus> - owner,
+    /// discriminator and order_id are validated in handler
+    #[account(mut)]
+    pub order_status: UncheckedAccount<'info>,
+
+    /// Pays the rent for the additional bytes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_trade_config_handler(ctx: Context<MigrateTradeConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let admin_info = ctx.accounts.admin.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+
+    let mut config: TradeConfig = if config_info.data_len() == LegacyTradeConfig::SPACE {
+        // Deployed v0 layout: fields after vault_program moved, so zero-extending would
+        // misread `relayers` as `treasury` - decode v0 and rebuild field by field
+        realloc_account(
+            &config_info,
+            &crate::ID,
+            TradeConfig::INIT_SPACE,
+            &admin_info,
+            &system_program_info,
+        )?;
+        let legacy = LegacyTradeConfig::try_from_bytes(&config_info.try_borrow_data()?)?;
+
+        // Fees start at 0 - treasury defaults to admin until update_treasury
+        let treasury = legacy.admin;
+        legacy.into_current(treasury)
+    } else {
+        // Grow account and load with the current layout (appended fields read as 0)
+        realloc_and_load(
+            &config_info,
+            &crate::ID,
+            TradeConfig::INIT_SPACE,
+            &admin_info,
+            &system_program_info,
+        )?
+    };
+
+    // Validate admin authority
+    require!(
+        config.admin == ctx.accounts.admin.key(),
+        TradingError::InvalidAdmin
+    );
+
+    let previous_version = config.version;
+    config.upgrade_version()?;
+    store_account(&config_info, &config)?;
+
+    emit_migrated(&config_info, "TradeConfig", previous_version, config.version, ctx.accounts.admin.key())
+}
+
+/// `accepted_collateral`: mints to accept after migration, empty keeps the current list
+/// Remaining accounts: vault CollateralMintConfig PDA for each mint (same order)
+pub fn migrate_token_market_handler(
+    ctx: Context<MigrateTokenMarket>,
+    accepted_collateral: Vec<Pubkey>,
+) -> Result<()> {
+    let token_market_info = ctx.accounts.token_market.to_account_info();
+
+    let mut token_market: TokenMarket = realloc_and_load(
+        &token_market_info,
+        &crate::ID,
+        8 + TokenMarket::INIT_SPACE,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // TokenMarket is a keypair account - token_id is its own address
+    require!(
+        token_market.token_id == token_market_info.key(),
+        TradingError::InvalidTokenAddress
+    );
+
+    let previous_version = token_market.version;
+    token_market.upgrade_version()?;
+
+    // Settlements before the upgrade were not counted - a mapped legacy market may
+    // already have delivered tokens, so it can never be remapped
+    if previous_version == 0 && token_market.is_mapped() {
+        token_market.settled_trade_count = TokenMarket::UNKNOWN_SETTLED_TRADE_COUNT;
+    }
+
+    if !accepted_collateral.is_empty() {
+        TokenMarket::validate_accepted_collateral(&accepted_collateral)?;
+        validate_collateral_mint_configs(
+            &accepted_collateral,
+            ctx.remaining_accounts,
+            &ctx.accounts.config.vault_program,
+        )?;
+        token_market.accepted_collateral = accepted_collateral.clone();
+
+        emit!(AcceptedCollateralUpdated {
+            token_id: token_market.token_id,
+            admin: ctx.accounts.admin.key(),
+            accepted_collateral,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    store_account(&token_market_info, &token_market)?;
+
+    emit_migrated(&token_market_info, "TokenMarket", previous_version, token_market.version, ctx.accounts.admin.key())
+}
+
+pub fn migrate_trade_record_handler(ctx: Context<MigrateTradeRecord>) -> Result<()> {
+    let trade_record_info = ctx.accounts.trade_record.to_account_info();
+
+    let mut trade_record: TradeRecord = realloc_and_load(
+        &trade_record_info,
+        &crate::ID,
+        8 + TradeRecord::INIT_SPACE,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    validate_trade_record_address(&trade_record, &trade_record_info.key())?;
+
+    // Pre-upgrade keypair records never stored a rent payer - refund rent to the migrating admin
+    // Their collateral was slashed out of `balance` at match (never locked), so settle /
+    // cancel / refund credit it back instead of unlocking
+    // Rates are the ones frozen by migrate_trade_config, not whatever the config holds today
+    if trade_record.version == 0 {
+        let config = &ctx.accounts.config;
+        require!(config.version > 0, TradingError::TradeConfigNotMigrated);
+        trade_record.rent_payer = ctx.accounts.admin.key();
+        trade_record.legacy_collateral = true;
+        trade_record.seller_reward_bps = config.legacy_seller_reward_bps;
+        trade_record.late_penalty_bps = config.legacy_late_penalty_bps;
+    }
+
+    let previous_version = trade_record.version;
+    trade_record.upgrade_version()?;
+    store_account(&trade_record_info, &trade_record)?;
+
+    emit_migrated(&trade_record_info, "TradeRecord", previous_version, trade_record.version, ctx.accounts.admin.key())
+}
+
+pub fn migrate_order_status_handler(ctx: Context<MigrateOrderStatus>) -> Result<()> {
+    let order_status_info = ctx.accounts.order_status.to_account_info();
+
+    let mut order_status: OrderStatus = realloc_and_load(
+        &order_status_info,
+        &crate::ID,
+        8 + OrderStatus::INIT_SPACE,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // order_id is the OrderStatus PDA address
+    require!(
+        order_status.order_id == order_status_info.key(),
+        TradingError::InvalidAccountOwner
+    );
+
+    let previous_version = order_status.version;
+    order_status.upgrade_version()?;
+    store_account(&order_status_info, &order_status)?;
+
+    emit_migrated(&order_status_info, "OrderStatus", previous_version, order_status.version, ctx.accounts.payer.key())
+}
+
+/// Validate TradeRecord address: PDA of its trade hash, or legacy keypair record
+/// Records created before versioning (`version == 0`) are keypair accounts without
+/// `trade_hash` / `bump` - owner and discriminator are checked on load, and the
+/// address must match the stored trade_id
+fn validate_trade_record_address(trade_record: &TradeRecord, address: &Pubkey) -> Result<()> {
+    if trade_record.version == 0 {
+        require!(trade_record.trade_id == *address, TradingError::InvalidAccountOwner);
+        return Ok(());
+    }
+
+    // Validate PDA derivation with stored bump
+    let expected = Pubkey::create_program_address(
+        &[
+            TradeRecord::TRADE_RECORD_SEED,
+            &trade_record.trade_hash,
+            &trade_record.fill_offset.to_le_bytes(),
+            &[trade_record.bump],
+        ],
+        &crate::ID,
+    ).map_err(|_| TradingError::InvalidAccountOwner)?;
+    require!(
+        expected == *address && trade_record.trade_id == expected,
+        TradingError::InvalidAccountOwner
+    );
+    Ok(())
+}
+
+/// Emit AccountMigrated and log the upgrade
+fn emit_migrated(
+    account: &AccountInfo,
+    account_type: &str,
+    previous_version: u8,
+    version: u8,
+    payer: Pubkey,
+) -> Result<()> {
+    emit!(AccountMigrated {
+        account: account.key(),
+        account_type: account_type.to_string(),
+        previous_version,
+        version,
+        payer,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "{} migrated: account={}, version={} -> {}",
+        account_type,
+        account.key(),
+        previous_version,
+        version
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{TechnicalConfig, CURRENT_ACCOUNT_VERSION};
+    use escrow_vault::migration::load_upgraded;
+
+    /// Deployed v0 TradeConfig bytes: admin, vault_program, relayers,
+    /// economic (2 u64 + 4 u16), technical (2 u32), paused, bump
+    fn v0_trade_config_bytes(admin: &Pubkey, vault_program: &Pubkey, relayers: &[Pubkey]) -> Vec<u8> {
+        let mut data = <TradeConfig as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(admin.as_ref());
+        data.extend_from_slice(vault_program.as_ref());
+        data.extend_from_slice(&(relayers.len() as u32).to_le_bytes());
+        for relayer in relayers {
+            data.extend_from_slice(relayer.as_ref());
+        }
+        data.extend_from_slice(&1_000u64.to_le_bytes()); // minimum_fill_amount
+        data.extend_from_slice(&1_000_000u64.to_le_bytes()); // maximum_order_amount
+        data.extend_from_slice(&10000u16.to_le_bytes()); // buyer_collateral_ratio
+        data.extend_from_slice(&12000u16.to_le_bytes()); // seller_collateral_ratio
+        data.extend_from_slice(&100u16.to_le_bytes()); // seller_reward_bps
+        data.extend_from_slice(&1000u16.to_le_bytes()); // late_penalty_bps
+        data.extend_from_slice(&3600u32.to_le_bytes()); // min_settle_time
+        data.extend_from_slice(&86400u32.to_le_bytes()); // max_settle_time
+        data.push(1); // paused
+        data.push(253); // bump
+        data.resize(LegacyTradeConfig::SPACE, 0);
+        data
+    }
+
+    #[test]
+    fn trade_config_v0_layout_is_rebuilt_field_by_field() {
+        let admin = Pubkey::new_unique();
+        let vault_program = Pubkey::new_unique();
+        let relayers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let v0 = v0_trade_config_bytes(&admin, &vault_program, &relayers);
+
+        // Zero-extending v0 bytes misreads relayers as treasury
+        let misread = load_upgraded::<TradeConfig>(&v0, TradeConfig::INIT_SPACE);
+        assert!(!matches!(misread, Ok(config) if config.relayers == relayers));
+
+        let mut upgraded = LegacyTradeConfig::try_from_bytes(&v0).unwrap().into_current(admin);
+        assert_eq!(upgraded.admin, admin);
+        assert_eq!(upgraded.vault_program, vault_program);
+        assert_eq!(upgraded.treasury, admin);
+        assert_eq!(upgraded.relayers, relayers);
+        assert_eq!(upgraded.economic_config.minimum_fill_amount, 1_000);
+        assert_eq!(upgraded.economic_config.maximum_order_amount, 1_000_000);
+        assert_eq!(upgraded.economic_config.buyer_collateral_ratio, 10000);
+        assert_eq!(upgraded.economic_config.seller_collateral_ratio, 12000);
+        assert_eq!(upgraded.economic_config.seller_reward_bps, 100);
+        assert_eq!(upgraded.economic_config.late_penalty_bps, 1000);
+        assert_eq!(upgraded.economic_config.buyer_fee_bps, 0);
+        assert_eq!(upgraded.economic_config.seller_fee_bps, 0);
+        assert_eq!(upgraded.technical_config.min_settle_time, 3600);
+        assert_eq!(upgraded.technical_config.max_settle_time, 86400);
+        assert_eq!(
+            upgraded.technical_config.trade_record_retention,
+            TechnicalConfig::default().trade_record_retention
+        );
+        assert!(upgraded.paused);
+        assert_eq!(upgraded.bump, 253);
+        assert_eq!(upgraded.pending_admin, None);
+        assert_eq!(upgraded.version, 0);
+
+        upgraded.upgrade_version().unwrap();
+        assert_eq!(upgraded.version, CURRENT_ACCOUNT_VERSION);
+        assert_eq!(upgraded.legacy_seller_reward_bps, 100);
+        assert_eq!(upgraded.legacy_late_penalty_bps, 1000);
+        assert!(upgraded.upgrade_version().is_err());
+
+        // Later rate changes do not touch the rates frozen for pre-upgrade trades
+        upgraded.economic_config.seller_reward_bps = 500;
+        upgraded.economic_config.late_penalty_bps = 5000;
+        assert_eq!(upgraded.legacy_seller_reward_bps, 100);
+        assert_eq!(upgraded.legacy_late_penalty_bps, 1000);
+
+        // Round trip through the current size
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        assert!(current.len() <= TradeConfig::INIT_SPACE);
+        let reloaded = TradeConfig::try_deserialize(&mut &current[..]).unwrap();
+        assert_eq!(reloaded.relayers, relayers);
+        assert_eq!(reloaded.economic_config.late_penalty_bps, 5000);
+        assert_eq!(reloaded.legacy_late_penalty_bps, 1000);
+    }
+
+    #[test]
+    fn trade_config_v0_with_full_relayer_list_fills_v0_space() {
+        let admin = Pubkey::new_unique();
+        let relayers: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        let v0 = v0_trade_config_bytes(&admin, &Pubkey::new_unique(), &relayers);
+        assert_eq!(v0.len(), LegacyTradeConfig::SPACE);
+
+        let upgraded = LegacyTradeConfig::try_from_bytes(&v0).unwrap().into_current(admin);
+        assert_eq!(upgraded.relayers, relayers);
+        assert_eq!(upgraded.bump, 253);
+
+        // Other account types are rejected by discriminator
+        let mut wrong = v0.clone();
+        wrong[..8].copy_from_slice(&<TradeRecord as anchor_lang::Discriminator>::DISCRIMINATOR);
+        assert!(LegacyTradeConfig::try_from_bytes(&wrong).is_err());
+    }
+
+    #[test]
+    fn legacy_keypair_trade_record_is_accepted() {
+        let trade_id = Pubkey::new_unique();
+
+        // v0 layout: keypair record without fees, trade_hash, bump or version
+        let mut v0 = <TradeRecord as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+        v0.extend_from_slice(trade_id.as_ref());
+        for _ in 0..4 {
+            v0.extend_from_slice(Pubkey::new_unique().as_ref()); // buyer, seller, token_id, collateral_mint
+        }
+        for value in [500u64, 2_000_000, 1_000, 1_000] {
+            v0.extend_from_slice(&value.to_le_bytes()); // filled_amount, price, collaterals
+        }
+        v0.extend_from_slice(&1_700_000_000i64.to_le_bytes()); // match_time
+        v0.push(0); // settled
+
+        let trade_record: TradeRecord = load_upgraded(&v0, TradeRecord::INIT_SPACE).unwrap();
+        assert_eq!(trade_record.version, 0);
+        assert_eq!(trade_record.trade_hash, [0; 32]);
+        assert_eq!(trade_record.filled_amount, 500);
+        assert_eq!(trade_record.match_time, 1_700_000_000);
+
+        // No PDA check for legacy records - address must still be the stored trade_id
+        assert!(validate_trade_record_address(&trade_record, &trade_id).is_ok());
+        assert!(validate_trade_record_address(&trade_record, &Pubkey::new_unique()).is_err());
+
+        // Versioned records must be the PDA of their trade hash
+        let mut versioned = trade_record;
+        versioned.version = CURRENT_ACCOUNT_VERSION;
+        assert!(validate_trade_record_address(&versioned, &trade_id).is_err());
+    }
+}
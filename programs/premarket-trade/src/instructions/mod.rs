@@ -1,21 +1,41 @@
 pub mod initialize;
 pub mod create_token_market;
 pub mod map_token;
+pub mod remap_token;
 pub mod update_config;
 pub mod manage_relayers;
 pub mod match_orders;
 pub mod settle_trade;
 pub mod cancel_trade;
+pub mod refund_voided_trade;
+pub mod close_trade_record;
 pub mod cancel_order;
+pub mod cancel_all_orders;
 pub mod emergency;
+pub mod set_order_signature_mode;
+pub mod transfer_admin;
+pub mod set_accepted_collateral;
+pub mod market_economic_config;
+pub mod market_status;
+pub mod migrate;
 
 pub use initialize::*;
 pub use create_token_market::*;
 pub use map_token::*;
+pub use remap_token::*;
 pub use update_config::*;
 pub use manage_relayers::*;
 pub use match_orders::*;
 pub use settle_trade::*;
 pub use cancel_trade::*;
+pub use refund_voided_trade::*;
+pub use close_trade_record::*;
 pub use cancel_order::*;
-pub use emergency::*; 
\ No newline at end of file
+pub use cancel_all_orders::*;
+pub use emergency::*;
+pub use set_order_signature_mode::*;
+pub use transfer_admin::*;
+pub use set_accepted_collateral::*;
+pub use market_economic_config::*;
+pub use market_status::*;
+pub use migrate::*; 
\ No newline at end of file
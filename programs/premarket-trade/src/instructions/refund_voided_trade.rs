@@ -0,0 +1,301 @@
+/*!
+ * # REFUND VOIDED TRADE INSTRUCTION
+ *
+ * ## 🎯 Business Purpose
+ * Unwinds trades of a market whose token launch was cancelled (`void_market`).
+ * Both sides get their collateral back without penalty - nobody is at fault.
+ *
+ * ## 🔄 Refund Flow
+ * 1. **Validation**: Market is Voided, trade not settled / cancelled / refunded
+ * 2. **Unlock**: Release unsettled buyer and seller collateral in the vault
+ * 3. **State Update**: Mark trade as settled (refunded)
+ * 4. **Event Emission**: Emit TradeRefunded event
+ *
+ * ## 🛡️ Security Requirements
+ * - Permissionless crank - funds only return to the trade's own buyer / seller
+ * - Buyer / seller balance PDAs derived from the TradeRecord (no substitution)
+ * - Each trade refunded once (marked settled)
+ *
+ * ## 💰 Economic Model
+ * - Only the unsettled remainder is refunded; chunks already delivered via
+ *   `settle_partial` keep their released amounts
+ * - Buyer gets: `unsettled buyer_collateral` back to free vault balance
+ * - Seller gets: `unsettled seller_collateral` back to free vault balance
+ * - Protocol fees already collected are not refunded
+ * - Pre-upgrade trades (`legacy_collateral`) are credited back instead of unlocked
+ * - Open orders hold no collateral (locked per fill only) and can no longer
+ *   be matched once the market is Voided, so there is nothing to release for them
+ *
+ * ## 📊 Event Data
+ * Emits `TradeRefunded` with refunded amounts for off-chain indexing
+ */
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::common::VAULT_SIGNER_SEED;
+use crate::error::TradingError;
+use crate::events::TradeRefunded;
+
+// Import vault program for CPI calls
+use escrow_vault::cpi;
+use escrow_vault::program::EscrowVault;
+
+#[derive(Accounts)]
+pub struct RefundVoidedTrade<'info> {
+    /// TradeRecord PDA to refund
+    #[account(
+        mut,
+        constraint = trade_record.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+        constraint = !trade_record.settled @ TradingError::TradeAlreadySettled,
+    )]
+    pub trade_record: Account<'info, TradeRecord>,
+
+    /// TokenMarket of the trade (must be Voided)
+    #[account(
+        constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+        constraint = token_market.token_id == trade_record.token_id @ TradingError::TokenMintMismatch,
+        constraint = token_market.is_voided() @ TradingError::InvalidMarketStatus,
+    )]
+    pub token_market: Account<'info, TokenMarket>,
+
+    /// Trade configuration PDA for pause validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Anyone can crank refunds
+    pub cranker: Signer<'info>,
+
+    // Vault program accounts for CPI calls
+    /// Vault program for cross-program calls
+    #[account(
+        constraint = vault_program.key() == config.vault_program @ TradingError::VaultProgramMismatch,
+    )]
+    pub vault_program: Program<'info, EscrowVault>,
+
+    /// Vault config PDA
+    #[account(
+        seeds = [escrow_vault::state::VaultConfig::VAULT_CONFIG_SEED],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub vault_config: Account<'info, escrow_vault::state::VaultConfig>,
+
+    /// Vault AuthorizedTrader PDA of this program (CPI authorization)
+    #[account(
+        seeds = [
+            escrow_vault::state::AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            crate::ID.as_ref()
+        ],
+        bump = authorized_trader.bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub authorized_trader: Box<Account<'info, escrow_vault::state::AuthorizedTrader>>,
+
+    /// Program signer PDA - signs vault CPIs (matched against AuthorizedTrader.signer)
+    /// CHECK: PDA of this program, holds no data
+    #[account(
+        seeds = [VAULT_SIGNER_SEED],
+        bump,
+    )]
+    pub vault_signer: UncheckedAccount<'info>,
+
+    /// Buyer balance PDA of the trade's buyer and collateral mint
+    /// CHECK: Seeds pin the account to trade_record.buyer, contents validated via CPI
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::UserBalance::USER_BALANCE_SEED,
+            trade_record.buyer.as_ref(),
+            trade_record.collateral_mint.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub buyer_balance: AccountInfo<'info>,
+
+    /// Seller balance PDA of the trade's seller and collateral mint
+    /// CHECK: Seeds pin the account to trade_record.seller, contents validated via CPI
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::UserBalance::USER_BALANCE_SEED,
+            trade_record.seller.as_ref(),
+            trade_record.collateral_mint.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub seller_balance: AccountInfo<'info>,
+
+    /// Vault authority PDA
+    #[account(
+        seeds = [
+            escrow_vault::state::VaultAuthority::VAULT_AUTHORITY_SEED,
+            trade_record.collateral_mint.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub vault_authority: Account<'info, escrow_vault::state::VaultAuthority>,
+}
+
+pub fn handler(ctx: Context<RefundVoidedTrade>) -> Result<()> {
+    let trade_record = &ctx.accounts.trade_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Only the unsettled remainder is refunded (partially settled chunks are final)
+    let refunded_amount = trade_record.remaining_amount();
+    let buyer_refund = trade_record.pro_rata_share(
+        trade_record.buyer_collateral,
+        trade_record.settled_amount,
+        trade_record.filled_amount,
+    )?;
+    let seller_refund = trade_record.pro_rata_share(
+        trade_record.seller_collateral,
+        trade_record.settled_amount,
+        trade_record.filled_amount,
+    )?;
+
+    // Step 1: Unlock collateral of both sides - no penalty
+    // (pre-upgrade trades had collateral slashed at match - credit it back instead)
+    if trade_record.legacy_collateral {
+        if buyer_refund > 0 {
+            credit_buyer_collateral_cpi(&ctx, buyer_refund)?;
+        }
+        if seller_refund > 0 {
+            credit_seller_collateral_cpi(&ctx, seller_refund)?;
+        }
+    } else {
+        if buyer_refund > 0 {
+            unlock_buyer_collateral_cpi(&ctx, buyer_refund)?;
+        }
+        if seller_refund > 0 {
+            unlock_seller_collateral_cpi(&ctx, seller_refund)?;
+        }
+    }
+
+    // Step 2: Update trade record state
+    let trade_record = &mut ctx.accounts.trade_record;
+    trade_record.settled = true;
+    trade_record.settled_at = current_time;
+
+    // Step 3: Emit TradeRefunded event
+    emit!(TradeRefunded {
+        trade_id: trade_record.trade_id,
+        token_id: trade_record.token_id,
+        buyer: trade_record.buyer,
+        seller: trade_record.seller,
+        collateral_mint: trade_record.collateral_mint,
+        refunded_amount,
+        buyer_refund,
+        seller_refund,
+        settled_amount: trade_record.settled_amount,
+        cranker: ctx.accounts.cranker.key(),
+        refund_time: current_time,
+    });
+
+    msg!(
+        "Voided trade refunded: trade_id: {} - buyer_refund: {} - seller_refund: {}",
+        trade_record.trade_id,
+        buyer_refund,
+        seller_refund
+    );
+
+    Ok(())
+}
+
+/// Unlock buyer collateral locked at match via CPI to vault program
+fn unlock_buyer_collateral_cpi(
+    ctx: &Context<RefundVoidedTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::UnlockBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.buyer_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+    cpi::unlock_balance(cpi_ctx, amount)?;
+
+    msg!("Buyer collateral refunded successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Unlock seller collateral locked at match via CPI to vault program
+fn unlock_seller_collateral_cpi(
+    ctx: &Context<RefundVoidedTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::UnlockBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.seller_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+    cpi::unlock_balance(cpi_ctx, amount)?;
+
+    msg!("Seller collateral refunded successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Credit buyer collateral of a pre-upgrade trade (slashed at match) via CPI to vault program
+fn credit_buyer_collateral_cpi(
+    ctx: &Context<RefundVoidedTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::CreditBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.buyer_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::credit_balance(cpi_ctx, amount)?;
+    
+    msg!("Legacy buyer collateral credited successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Credit seller collateral of a pre-upgrade trade (slashed at match) via CPI to vault program
+fn credit_seller_collateral_cpi(
+    ctx: &Context<RefundVoidedTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::CreditBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.seller_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::credit_balance(cpi_ctx, amount)?;
+    
+    msg!("Legacy seller collateral credited successfully via CPI: {}", amount);
+    Ok(())
+}
@@ -0,0 +1,263 @@
+/*!
+ * # REMAP TOKEN INSTRUCTIONS
+ *
+ * ## 🎯 Business Purpose
+ * Corrects a wrong `map_token` (wrong mint or conversion ratio) before any seller
+ * delivered tokens, instead of forcing every trade into penalty cancellation.
+ *
+ * ## 🔄 Remap Flow
+ * 1. `schedule_token_remap`: admin queues the corrected mapping with a reason code
+ * 2. `execute_token_remap`: anyone applies it once `TOKEN_REMAP_DELAY` has passed
+ * 3. `cancel_token_remap`: admin discards it
+ *
+ * ## 🛡️ Security Requirements
+ * - Only admin can schedule / cancel a remap
+ * - Market must be mapped, not Closed / Voided and have no settled trade
+ *   (`settled_trade_count == 0`) both when scheduling and when executing
+ * - Markets mapped before the settled trade counter existed are never remappable
+ *   (`migrate_token_market` sets `UNKNOWN_SETTLED_TRADE_COUNT`)
+ * - Mandatory delay so traders see the correction before it applies
+ * - `settle_trade` / `cancel_trade` are rejected while a remap is queued, so no
+ *   trade settles against the mapping being corrected
+ *
+ * ## ⏰ Settlement Window
+ * Execution resets `mapping_time`, so settlement deadlines restart from the corrected mapping.
+ *
+ * ## 📈 Event Emission
+ * Emits `TokenRemapScheduled`, `TokenRemapCancelled` and `TokenRemapped`
+ */
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::error::TradingError;
+use crate::events::{TokenRemapCancelled, TokenRemapScheduled, TokenRemapped};
+use crate::common::TokenRemapParams;
+
+#[derive(Accounts)]
+#[instruction(params: TokenRemapParams)]
+pub struct ScheduleTokenRemap<'info> {
+    /// TokenMarket to correct
+    #[account(
+        constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+    )]
+    pub token_market: Account<'info, TokenMarket>,
+
+    /// Queued remap PDA (init fails while another remap is queued for the market)
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + QueuedTokenRemap::INIT_SPACE,
+        seeds = [QueuedTokenRemap::QUEUED_TOKEN_REMAP_SEED, token_market.key().as_ref()],
+        bump,
+    )]
+    pub queued_remap: Account<'info, QueuedTokenRemap>,
+
+    /// Corrected real token mint (SPL Token or Token-2022)
+    #[account(
+        address = params.real_mint @ TradingError::InvalidTokenMint,
+    )]
+    pub real_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Trade configuration PDA for admin validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Admin signer (must match config.admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTokenRemap<'info> {
+    /// TokenMarket to correct
+    #[account(
+        mut,
+        constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+    )]
+    pub token_market: Account<'info, TokenMarket>,
+
+    /// Queued remap PDA (closed on execution)
+    #[account(
+        mut,
+        close = rent_receiver,
+        seeds = [QueuedTokenRemap::QUEUED_TOKEN_REMAP_SEED, token_market.key().as_ref()],
+        bump = queued_remap.bump,
+    )]
+    pub queued_remap: Account<'info, QueuedTokenRemap>,
+
+    /// Corrected real token mint (decimals snapshot)
+    #[account(
+        address = queued_remap.real_mint @ TradingError::InvalidTokenMint,
+    )]
+    pub real_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Trade configuration PDA for pause validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// CHECK: Admin that scheduled the remap - receives the rent back
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == queued_remap.scheduled_by @ TradingError::InvalidRentPayer,
+    )]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// Anyone can execute once effective
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelTokenRemap<'info> {
+    /// Queued remap PDA (closed, rent refunded to admin)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [QueuedTokenRemap::QUEUED_TOKEN_REMAP_SEED, queued_remap.token_market.as_ref()],
+        bump = queued_remap.bump,
+    )]
+    pub queued_remap: Account<'info, QueuedTokenRemap>,
+
+    /// Trade configuration PDA for admin validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Admin signer (must match config.admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Queue a corrected mapping for a market
+pub fn schedule_handler(
+    ctx: Context<ScheduleTokenRemap>,
+    params: TokenRemapParams,
+) -> Result<()> {
+    let token_market = &ctx.accounts.token_market;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Step 1: Validate market can still be corrected and the new mapping
+    token_market.validate_remap()?;
+    require!(
+        params.conversion_numerator > 0 && params.conversion_denominator > 0,
+        TradingError::InvalidConversionRatio
+    );
+    require!(
+        escrow_vault::utils::is_supported_mint(&ctx.accounts.real_mint.to_account_info())?,
+        TradingError::UnsupportedMintExtension
+    );
+
+    // Step 2: Queue remap
+    let queued_remap = &mut ctx.accounts.queued_remap;
+    queued_remap.initialize(
+        token_market.key(),
+        &params,
+        ctx.accounts.admin.key(),
+        current_time,
+        ctx.bumps.queued_remap,
+    );
+
+    emit!(TokenRemapScheduled {
+        token_id: token_market.token_id,
+        current_real_mint: token_market.real_mint.unwrap_or_default(),
+        new_real_mint: params.real_mint,
+        conversion_numerator: params.conversion_numerator,
+        conversion_denominator: params.conversion_denominator,
+        reason: params.reason,
+        admin: ctx.accounts.admin.key(),
+        scheduled_at: current_time,
+        effective_at: queued_remap.effective_at,
+    });
+
+    msg!(
+        "Token remap scheduled: market_id: {} -> real_mint: {} ({:?}), effective at: {}",
+        token_market.token_id,
+        params.real_mint,
+        params.reason,
+        queued_remap.effective_at
+    );
+
+    Ok(())
+}
+
+/// Apply a queued remap once its delay has elapsed
+pub fn execute_handler(ctx: Context<ExecuteTokenRemap>) -> Result<()> {
+    let queued_remap = &ctx.accounts.queued_remap;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Step 1: Check mandatory delay
+    require!(
+        queued_remap.is_ready(current_time),
+        TradingError::TokenRemapNotReady
+    );
+
+    // Step 2: Re-map (fails if a trade settled during the delay)
+    let token_market = &mut ctx.accounts.token_market;
+    let real_mint_decimals = ctx.accounts.real_mint.decimals;
+    let previous_real_mint = token_market.remap_token(
+        queued_remap.real_mint,
+        real_mint_decimals,
+        queued_remap.conversion_numerator,
+        queued_remap.conversion_denominator,
+        current_time,
+    )?;
+
+    emit!(TokenRemapped {
+        token_id: token_market.token_id,
+        previous_real_mint,
+        real_mint: queued_remap.real_mint,
+        real_mint_decimals,
+        conversion_numerator: queued_remap.conversion_numerator,
+        conversion_denominator: queued_remap.conversion_denominator,
+        reason: queued_remap.reason,
+        admin: queued_remap.scheduled_by,
+        executed_by: ctx.accounts.executor.key(),
+        mapping_time: current_time,
+        settle_time_limit: token_market.settle_time_limit,
+    });
+
+    msg!(
+        "Token remapped: market_id: {} - {} -> {} ({:?})",
+        token_market.token_id,
+        previous_real_mint,
+        queued_remap.real_mint,
+        queued_remap.reason
+    );
+
+    Ok(())
+}
+
+/// Discard a queued remap
+pub fn cancel_handler(ctx: Context<CancelTokenRemap>) -> Result<()> {
+    let queued_remap = &ctx.accounts.queued_remap;
+
+    emit!(TokenRemapCancelled {
+        token_id: queued_remap.token_market,
+        real_mint: queued_remap.real_mint,
+        reason: queued_remap.reason,
+        admin: ctx.accounts.admin.key(),
+        cancelled_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Token remap cancelled: market_id: {} - real_mint: {}",
+        queued_remap.token_market,
+        queued_remap.real_mint
+    );
+
+    Ok(())
+}
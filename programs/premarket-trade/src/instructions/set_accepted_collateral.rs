@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::TradingError;
+use crate::events::AcceptedCollateralUpdated;
+use crate::utils::validate_collateral_mint_configs;
+
+#[derive(Accounts)]
+pub struct SetAcceptedCollateral<'info> {
+    /// TokenMarket account to update
+    #[account(
+        mut,
+        constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+    )]
+    pub token_market: Account<'info, TokenMarket>,
+
+    /// Trade configuration PDA for admin validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Admin signer (must match config.admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Remaining accounts: vault CollateralMintConfig PDA for each accepted collateral mint (same order)
+pub fn handler(
+    ctx: Context<SetAcceptedCollateral>,
+    accepted_collateral: Vec<Pubkey>,
+) -> Result<()> {
+    TokenMarket::validate_accepted_collateral(&accepted_collateral)?;
+    validate_collateral_mint_configs(
+        &accepted_collateral,
+        ctx.remaining_accounts,
+        &ctx.accounts.config.vault_program,
+    )?;
+
+    let token_market = &mut ctx.accounts.token_market;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    token_market.accepted_collateral = accepted_collateral.clone();
+
+    emit!(AcceptedCollateralUpdated {
+        token_id: token_market.token_id,
+        admin: ctx.accounts.admin.key(),
+        accepted_collateral,
+        timestamp,
+    });
+
+    msg!(
+        "Accepted collateral updated: token_id: {} - mints: {}",
+        token_market.token_id,
+        token_market.accepted_collateral.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::TradingError;
+use crate::events::OrderSignatureModeUpdated;
+
+#[derive(Accounts)]
+pub struct SetOrderSignatureMode<'info> {
+    /// TokenMarket account to update
+    #[account(
+        mut,
+        constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
+    )]
+    pub token_market: Account<'info, TokenMarket>,
+
+    /// Trade configuration PDA for admin validation
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+
+    /// Admin signer (must match config.admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetOrderSignatureMode>,
+    require_order_signatures: bool,
+) -> Result<()> {
+    let token_market = &mut ctx.accounts.token_market;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    token_market.require_order_signatures = require_order_signatures;
+
+    emit!(OrderSignatureModeUpdated {
+        token_id: token_market.token_id,
+        admin: ctx.accounts.admin.key(),
+        require_order_signatures,
+        timestamp,
+    });
+
+    msg!(
+        "Order signature mode updated: token_id: {} - require_order_signatures: {}",
+        token_market.token_id,
+        require_order_signatures
+    );
+
+    Ok(())
+}
@@ -2,42 +2,67 @@
  * # SETTLE TRADE INSTRUCTION
  * 
  * ## 🎯 Business Purpose
- * Allows seller to deliver real tokens to buyer and receive collateral + reward.
+ * Allows seller to deliver real tokens to buyer and receive the buyer's locked
+ * payment, their own collateral back and a reward.
  * This is the successful completion path of a premarket trade.
+ * `settle_partial` delivers a chunk and releases the matching pro-rata share,
+ * so a seller holding only part of the tokens can settle before the deadline.
  * 
  * ## 🔄 Settlement Flow
  * 1. **Validation**: Check seller authority, grace period, token mapping
- * 2. **Token Transfer**: Transfer real tokens from seller → buyer
- * 3. **Reward Calculation**: Calculate seller reward based on economic config
- * 4. **Collateral Release**: Release seller collateral + reward via CPI to vault
- * 5. **State Update**: Mark trade as settled
- * 6. **Event Emission**: Emit TradeSettled event
+ * 2. **Token Transfer**: Transfer real tokens (full remainder or chunk) from seller → buyer
+ * 3. **Reward Calculation**: Calculate pro-rata seller reward, collateral and payment
+ * 4. **Collateral Unlock**: Unlock seller collateral and buyer payment, move payment to seller via CPI
+ * 5. **Fee Collection**: Pay seller fee to treasury via CPI to vault
+ * 6. **Payout**: Keep proceeds in seller vault balance or transfer to wallet (selectable)
+ * 7. **Reward Payment**: Pay seller reward from the vault reward pool via CPI
+ * 8. **State Update**: Track `settled_amount`, mark trade settled once fully delivered
+ * 9. **Event Emission**: Emit TradeSettled event
  * 
  * ## 🛡️ Security Requirements
  * - Only seller can settle their own trades
  * - Settlement must happen within grace period
+ * - Grace period starts at max(match_time, mapping_time, settlement_started_at), not at match
  * - TokenMarket must be mapped to real token mint
+ * - No token remap may be queued for the market
  * - Seller must have sufficient real tokens
  * - All token accounts must match expected mints
  * 
  * ## 💰 Economic Model
- * - Seller gets back: `original_collateral + seller_reward`
- * - Seller reward = `trade_value * seller_reward_bps / 10000`
- * - Buyer gets: `filled_amount` of real tokens
- * - Buyer collateral remains locked (will be released separately)
+ * - Seller gets: `buyer_collateral + seller_collateral - seller_fee`
+ *   (credited to vault balance or transferred to wallet per `SettlementPayout`)
+ * - Treasury gets: `seller_fee` (fixed at match time on the TradeRecord)
+ * - Seller reward = `trade_value * seller_reward_bps / 10000` (rate snapshotted on the
+ *   TradeRecord at match), paid from the
+ *   admin-funded reward pool of the collateral mint (never from user balances)
+ * - If the pool is missing or underfunded the reward is refused: settlement
+ *   still completes and the shortfall is reported as `unpaid_reward`
+ * - Buyer gets: `filled_amount` converted to real base units with the market's
+ *   mapping ratio (`conversion_numerator / conversion_denominator`), converted on
+ *   cumulative totals and rounded down so chunks never over-deliver and the
+ *   chunk crossing a whole base unit carries the dust of earlier chunks
+ * - The whole `filled_amount` must convert exactly: trades matched before the
+ *   mapping (or a remap) with a non-convertible size are rejected, never under-delivered
+ * - Buyer collateral (the purchase payment) moves from the buyer's lock to the seller
+ * - Each chunk releases `total * delivered / filled_amount` of every amount above,
+ *   computed on cumulative totals so the last chunk releases any rounding dust
+ * - Trades matched before the upgrade (`legacy_collateral`) had both collaterals
+ *   slashed instead of locked - the seller's share is credited back instead of unlocked
  * 
  * ## 🔗 Cross-Program Integration
  * - Uses CPI to vault program for collateral release
+ * - Uses CPI to vault program `pay_reward` for seller reward
  * - Direct token transfer for real token delivery
  * - Follows exact EVM business logic mapping
  * 
  * ## 📊 Event Data
- * Emits `TradeSettled` with trade details for off-chain indexing
+ * Emits `TradeSettled` with both legs (token delivery and payment) for off-chain indexing
  */
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
+use crate::common::{SettlementPayout, VAULT_SIGNER_SEED};
 use crate::error::TradingError;
 use crate::events::TradeSettled;
 
@@ -47,7 +72,7 @@ use escrow_vault::program::EscrowVault;
 
 #[derive(Accounts)]
 pub struct SettleTrade<'info> {
-    /// TradeRecord account to settle (User-controlled keypair)
+    /// TradeRecord PDA to settle
     #[account(
         mut,
         constraint = trade_record.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
@@ -58,12 +83,23 @@ pub struct SettleTrade<'info> {
     
     /// TokenMarket for the trading pair (must be mapped to real token)
     #[account(
+        mut,
         constraint = token_market.to_account_info().owner == &crate::ID @ TradingError::InvalidAccountOwner,
         constraint = token_market.token_id == trade_record.token_id @ TradingError::TokenMintMismatch,
         constraint = token_market.real_mint.is_some() @ TradingError::TokenNotMapped,
+        constraint = token_market.is_settling() @ TradingError::InvalidMarketStatus,
     )]
     pub token_market: Box<Account<'info, TokenMarket>>,
     
+    /// QueuedTokenRemap PDA of the market - must not exist (no delivery against a mapping being corrected)
+    /// CHECK: Seeds pin the address, only emptiness is checked
+    #[account(
+        seeds = [QueuedTokenRemap::QUEUED_TOKEN_REMAP_SEED, token_market.key().as_ref()],
+        bump,
+        constraint = queued_remap.data_is_empty() @ TradingError::TokenRemapPending,
+    )]
+    pub queued_remap: UncheckedAccount<'info>,
+    
     /// Trade configuration PDA for validation
     #[account(
         seeds = [TradeConfig::TRADE_CONFIG_SEED],
@@ -91,11 +127,58 @@ pub struct SettleTrade<'info> {
     )]
     pub vault_config: Box<Account<'info, escrow_vault::state::VaultConfig>>,
     
-    /// Seller balance PDA for collateral release
-    /// CHECK: Seller balance account validated via CPI to vault program
+    /// Vault AuthorizedTrader PDA of this program (CPI authorization)
+    #[account(
+        seeds = [
+            escrow_vault::state::AuthorizedTrader::AUTHORIZED_TRADER_SEED,
+            crate::ID.as_ref()
+        ],
+        bump = authorized_trader.bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub authorized_trader: Box<Account<'info, escrow_vault::state::AuthorizedTrader>>,
+    
+    /// Program signer PDA - signs vault CPIs (matched against AuthorizedTrader.signer)
+    /// CHECK: PDA of this program, holds no data
+    #[account(
+        seeds = [VAULT_SIGNER_SEED],
+        bump,
+    )]
+    pub vault_signer: UncheckedAccount<'info>,
+    
+    /// Vault outflow limit of this program for the collateral mint (may not exist)
+    /// CHECK: PDA derivation validated via CPI to vault program
     #[account(mut)]
+    pub outflow_limit: AccountInfo<'info>,
+    
+    /// Seller balance PDA for collateral release and buyer payment
+    /// CHECK: Seeds pin the account to trade_record.seller, contents validated via CPI
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::UserBalance::USER_BALANCE_SEED,
+            trade_record.seller.as_ref(),
+            trade_record.collateral_mint.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
     pub seller_balance: AccountInfo<'info>,
     
+    /// Buyer balance PDA - locked payment is released to seller
+    /// CHECK: Seeds pin the account to trade_record.buyer, contents validated via CPI
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::UserBalance::USER_BALANCE_SEED,
+            trade_record.buyer.as_ref(),
+            trade_record.collateral_mint.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub buyer_balance: AccountInfo<'info>,
+    
     /// Vault authority PDA
     #[account(
         mut,
@@ -113,7 +196,7 @@ pub struct SettleTrade<'info> {
         mut,
         constraint = vault_ata.mint == trade_record.collateral_mint @ TradingError::TokenMintMismatch,
     )]
-    pub vault_ata: Box<Account<'info, TokenAccount>>,
+    pub vault_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// Seller ATA for collateral release
     #[account(
@@ -121,7 +204,34 @@ pub struct SettleTrade<'info> {
         constraint = seller_collateral_ata.owner == seller.key() @ TradingError::InvalidAccountOwner,
         constraint = seller_collateral_ata.mint == trade_record.collateral_mint @ TradingError::TokenMintMismatch,
     )]
-    pub seller_collateral_ata: Account<'info, TokenAccount>,
+    pub seller_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Treasury ATA for seller fee
+    #[account(
+        mut,
+        constraint = treasury_collateral_ata.owner == config.treasury @ TradingError::InvalidTreasury,
+        constraint = treasury_collateral_ata.mint == trade_record.collateral_mint @ TradingError::TokenMintMismatch,
+    )]
+    pub treasury_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Collateral mint (required by vault transfer_checked)
+    #[account(
+        address = trade_record.collateral_mint @ TradingError::TokenMintMismatch,
+        mint::token_program = token_program,
+    )]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// Reward pool PDA for the collateral mint (optional - reward unpaid when absent)
+    #[account(
+        mut,
+        seeds = [
+            escrow_vault::state::RewardPool::REWARD_POOL_SEED,
+            trade_record.collateral_mint.as_ref()
+        ],
+        bump,
+        seeds::program = vault_program.key(),
+    )]
+    pub reward_pool: Option<Box<Account<'info, escrow_vault::state::RewardPool>>>,
     
     // Real token transfer accounts
     /// Seller ATA for real token (source)
@@ -130,7 +240,7 @@ pub struct SettleTrade<'info> {
         constraint = seller_token_ata.owner == seller.key() @ TradingError::InvalidAccountOwner,
         constraint = seller_token_ata.mint == token_market.real_mint.unwrap() @ TradingError::TokenMintMismatch,
     )]
-    pub seller_token_ata: Account<'info, TokenAccount>,
+    pub seller_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
     /// Buyer ATA for real token (destination)
     #[account(
@@ -138,126 +248,236 @@ pub struct SettleTrade<'info> {
         constraint = buyer_token_ata.owner == trade_record.buyer @ TradingError::InvalidAccountOwner,
         constraint = buyer_token_ata.mint == token_market.real_mint.unwrap() @ TradingError::TokenMintMismatch,
     )]
-    pub buyer_token_ata: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub buyer_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    /// 🛡️ INSTRUCTION SYSVAR - For precise CPI caller detection
-    /// CHECK: Validated by constraint to ensure it's the instruction sysvar
+    /// Real token mint (required by transfer_checked)
     #[account(
-        constraint = instruction_sysvar.key() == solana_program::sysvar::instructions::ID @ TradingError::InvalidInstructionSysvar
+        address = token_market.real_mint.unwrap() @ TradingError::TokenMintMismatch,
+        mint::token_program = real_token_program,
     )]
-    pub instruction_sysvar: AccountInfo<'info>,
+    pub real_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// Token program of the collateral mint (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// Token program of the real token mint (may differ from collateral)
+    pub real_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SettleTrade>, payout: SettlementPayout) -> Result<()> {
+    // Full settlement delivers whatever is still outstanding
+    let amount = ctx.accounts.trade_record.remaining_amount();
+    settle(ctx, amount, payout)
 }
 
-pub fn handler(ctx: Context<SettleTrade>) -> Result<()> {
+/// Partial settlement: deliver `amount` tokens and release the pro-rata share
+pub fn settle_partial_handler(
+    ctx: Context<SettleTrade>,
+    amount: u64,
+    payout: SettlementPayout,
+) -> Result<()> {
+    settle(ctx, amount, payout)
+}
+
+fn settle(ctx: Context<SettleTrade>, amount: u64, payout: SettlementPayout) -> Result<()> {
     let trade_record = &ctx.accounts.trade_record;
     let token_market = &ctx.accounts.token_market;
-    let config = &ctx.accounts.config;
     
     // Get current time for validation
     let current_time = Clock::get()?.unix_timestamp;
     
-    // Validate grace period (settlement must happen within grace period)
-    let grace_period_end = trade_record.match_time + (token_market.settle_time_limit as i64);
+    // Validate grace period (window starts at max(match_time, mapping_time, settlement_started_at))
+    let settlement_deadline = trade_record
+        .effective_settlement_deadline(token_market)
+        .ok_or(TradingError::TokenNotMapped)?;
     require!(
-        current_time <= grace_period_end,
+        current_time <= settlement_deadline,
         TradingError::GracePeriodExpired
     );
     
+    // Validate chunk size against outstanding amount
+    require!(
+        amount > 0 && amount <= trade_record.remaining_amount(),
+        TradingError::InvalidSettlementAmount
+    );
+    
+    let settled_before = trade_record.settled_amount;
+    let settled_after = settled_before
+        .checked_add(amount)
+        .ok_or(TradingError::MathOverflow)?;
+    
+    // The full trade must convert exactly - match_orders only checks this when already
+    // mapped, so trades matched before map / remap are checked here instead of under-delivered
+    token_market.to_real_amount(trade_record.filled_amount)?;
+    
+    // Convert pre-market units to real token base units on cumulative totals
+    // (rounded down - chunk rounding dust is delivered by a later chunk)
+    let delivered_amount = token_market.real_amount_between(settled_before, settled_after)?;
+    
+    // Seller covers any Token-2022 transfer fee so buyer receives exactly `delivered_amount`
+    let delivery_fee = escrow_vault::utils::calculate_inverse_transfer_fee(
+        &ctx.accounts.real_mint.to_account_info(),
+        delivered_amount,
+    )?;
+    let gross_delivery = delivered_amount
+        .checked_add(delivery_fee)
+        .ok_or(TradingError::MathOverflow)?;
+    
     // Validate seller has sufficient real tokens
     require!(
-        ctx.accounts.seller_token_ata.amount >= trade_record.filled_amount,
+        ctx.accounts.seller_token_ata.amount >= gross_delivery,
         TradingError::InsufficientBalance
     );
     
     // Step 1: Transfer real tokens from seller to buyer
     msg!(
-        "Transferring {} real tokens from seller to buyer",
-        trade_record.filled_amount
+        "Transferring {} real tokens (+{} transfer fee) from seller to buyer",
+        delivered_amount,
+        delivery_fee
     );
     
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            ctx.accounts.real_token_program.to_account_info(),
+            TransferChecked {
                 from: ctx.accounts.seller_token_ata.to_account_info(),
+                mint: ctx.accounts.real_mint.to_account_info(),
                 to: ctx.accounts.buyer_token_ata.to_account_info(),
                 authority: ctx.accounts.seller.to_account_info(),
             },
         ),
-        trade_record.filled_amount,
+        gross_delivery,
+        ctx.accounts.real_mint.decimals,
     )?;
     
-    // Step 2: Calculate seller reward and total collateral release
-    let (seller_reward, total_seller_release) = calculate_settlement_amounts(
-        trade_record.filled_amount,
-        trade_record.price,
-        trade_record.seller_collateral,
-        &config.economic_config,
-    )?;
+    // Step 2: Calculate pro-rata seller reward, collateral release and buyer payment
+    let (seller_reward, seller_release, buyer_payment, chunk_seller_fee) =
+        calculate_settlement_amounts(
+            trade_record,
+            settled_before,
+            settled_after,
+        )?;
     
-    // Step 3: Release seller collateral + reward via CPI to vault
-    if total_seller_release > 0 {
-        msg!(
-            "Releasing {} collateral + {} reward = {} total to seller via CPI",
-            trade_record.seller_collateral,
-            seller_reward,
-            total_seller_release
-        );
+    // Step 3: Unlock seller collateral and move buyer payment into seller balance
+    let seller_proceeds = seller_release
+        .checked_add(buyer_payment)
+        .ok_or(TradingError::MathOverflow)?;
+    
+    if trade_record.legacy_collateral {
+        // Pre-upgrade trade: both collaterals were slashed at match, nothing is locked
+        if seller_proceeds > 0 {
+            msg!("Crediting {} legacy collateral + payment to seller balance via CPI", seller_proceeds);
+            credit_seller_proceeds_cpi(&ctx, seller_proceeds)?;
+        }
+    } else {
+        if seller_release > 0 {
+            msg!("Unlocking {} seller collateral via CPI", seller_release);
+            unlock_seller_collateral_cpi(&ctx, seller_release)?;
+        }
         
-        release_seller_collateral_cpi(&ctx, total_seller_release)?;
+        if buyer_payment > 0 {
+            msg!("Moving {} locked buyer payment to seller balance via CPI", buyer_payment);
+            transfer_buyer_payment_cpi(&ctx, buyer_payment)?;
+        }
     }
     
-    // Step 4: Update trade record state
+    // Step 4: Pay seller fee to treasury via CPI to vault (fee fixed at match time)
+    let seller_fee = chunk_seller_fee.min(seller_proceeds);
+    let seller_payout = seller_proceeds - seller_fee;
+    
+    if seller_fee > 0 {
+        pay_seller_fee_cpi(&ctx, seller_fee)?;
+    }
+    
+    // Step 5: Pay out proceeds to wallet if requested (otherwise they stay in vault balance)
+    if payout == SettlementPayout::Wallet && seller_payout > 0 {
+        release_seller_collateral_cpi(&ctx, seller_payout)?;
+    }
+    
+    // Step 6: Pay seller reward from reward pool (refused when underfunded)
+    let reward_paid = match ctx.accounts.reward_pool.as_ref() {
+        Some(reward_pool) if seller_reward > 0 && reward_pool.can_pay(seller_reward) => {
+            pay_seller_reward_cpi(&ctx, seller_reward)?;
+            seller_reward
+        }
+        _ => 0,
+    };
+    let unpaid_reward = seller_reward - reward_paid;
+    if unpaid_reward > 0 {
+        msg!("Reward pool cannot cover seller reward: unpaid: {}", unpaid_reward);
+    }
+    
+    // Step 7: Update trade record state (settled once fully delivered)
+    if settled_before == 0 {
+        ctx.accounts.token_market.record_settled_trade();
+    }
+    let token_market = &ctx.accounts.token_market;
     let trade_record = &mut ctx.accounts.trade_record;
-    trade_record.settled = true;
-    // trade_record.target_mint = Some(token_market.real_mint.unwrap());
+    trade_record.record_settlement(amount)?;
+    trade_record.settlement_deadline = settlement_deadline;
+    if trade_record.settled {
+        trade_record.settled_at = current_time;
+    }
     
-    // Step 5: Emit TradeSettled event
+    // Step 8: Emit TradeSettled event
     emit!(TradeSettled {
         trade_id: trade_record.trade_id,
         token_id: trade_record.token_id,        // EVM compatible naming
         buyer: trade_record.buyer,
         seller: trade_record.seller,
         target_mint: token_market.real_mint.unwrap(),
-        // target_mint: trade_record.target_mint.unwrap(),
-        filled_amount: trade_record.filled_amount,
-        seller_reward,
+        filled_amount: amount,
+        delivered_amount,
+        delivery_fee,
+        total_settled_amount: trade_record.settled_amount,
+        remaining_amount: trade_record.remaining_amount(),
+        buyer_payment,
+        seller_collateral_released: seller_release,
+        seller_payout,
+        payout,
+        seller_reward: reward_paid,
+        unpaid_reward,
+        seller_fee,
         settlement_time: current_time,
+        settlement_deadline,
     });
     
     msg!(
-        "Trade settled successfully: trade_id: {} - seller: {} - buyer: {} - amount: {} - reward: {}",
+        "Trade settled: trade_id: {} - seller: {} - buyer: {} - amount: {} ({} real units) - settled: {}/{} - reward: {}",
         trade_record.trade_id,
         trade_record.seller,
         trade_record.buyer,
+        amount,
+        delivered_amount,
+        trade_record.settled_amount,
         trade_record.filled_amount,
-        seller_reward
+        reward_paid
     );
     
     Ok(())
 }
 
-/// Calculate settlement amounts: seller reward and total release
+/// Calculate pro-rata settlement amounts for tokens delivered between `from` and `to`:
+/// seller reward, seller collateral release, buyer payment and seller fee
+/// Reward is returned separately - it is funded by the reward pool, not the seller balance
+/// Reward rate is the one snapshotted on the TradeRecord at match
 fn calculate_settlement_amounts(
-    filled_amount: u64,
-    price: u64,
-    seller_collateral: u64,
-    economic_config: &crate::common::EconomicConfig,
-) -> Result<(u64, u64)> {
+    trade_record: &TradeRecord,
+    from: u64,
+    to: u64,
+) -> Result<(u64, u64, u64, u64)> {
     // Calculate trade value
-    let trade_value = filled_amount
-        .checked_mul(price)
+    let trade_value = trade_record.filled_amount
+        .checked_mul(trade_record.price)
         .ok_or(TradingError::MathOverflow)?
         .checked_div(crate::common::PRICE_SCALE)
         .ok_or(TradingError::MathOverflow)?;
     
     // Calculate seller reward (basis points)
-    let seller_reward = if economic_config.seller_reward_bps > 0 {
+    let total_reward = if trade_record.seller_reward_bps > 0 {
         trade_value
-            .checked_mul(economic_config.seller_reward_bps as u64)
+            .checked_mul(trade_record.seller_reward_bps as u64)
             .ok_or(TradingError::MathOverflow)?
             .checked_div(10000)
             .ok_or(TradingError::MathOverflow)?
@@ -265,39 +485,265 @@ fn calculate_settlement_amounts(
         0
     };
     
-    // Total release = original collateral + reward
-    let total_seller_release = seller_collateral
-        .checked_add(seller_reward)
-        .ok_or(TradingError::MathOverflow)?;
+    let seller_reward = trade_record.pro_rata_share(total_reward, from, to)?;
+    let seller_release = trade_record.pro_rata_share(trade_record.seller_collateral, from, to)?;
+    let buyer_payment = trade_record.pro_rata_share(trade_record.buyer_collateral, from, to)?;
+    let seller_fee = trade_record.pro_rata_share(trade_record.seller_fee, from, to)?;
     
-    Ok((seller_reward, total_seller_release))
+    Ok((seller_reward, seller_release, buyer_payment, seller_fee))
 }
 
-/// Release seller collateral + reward via CPI to vault program
+/// Unlock seller collateral back to seller free balance via CPI to vault program
+fn unlock_seller_collateral_cpi(
+    ctx: &Context<SettleTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::UnlockBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.seller_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::unlock_balance(cpi_ctx, amount)?;
+    
+    msg!("Seller collateral unlocked successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Credit seller collateral + buyer payment of a pre-upgrade trade (slashed at match) via CPI to vault program
+fn credit_seller_proceeds_cpi(
+    ctx: &Context<SettleTrade>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::CreditBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.seller_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::credit_balance(cpi_ctx, amount)?;
+    
+    msg!("Legacy seller proceeds credited successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Unlock buyer payment and move it to seller free balance via CPI to vault program
+fn transfer_buyer_payment_cpi(
+    ctx: &Context<SettleTrade>,
+    amount: u64,
+) -> Result<()> {
+    let unlock_accounts = cpi::accounts::UnlockBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.buyer_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    
+    cpi::unlock_balance(
+        CpiContext::new_with_signer(ctx.accounts.vault_program.to_account_info(), unlock_accounts, signer_seeds),
+        amount,
+    )?;
+    
+    let cpi_accounts = cpi::accounts::TransferBalance {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        from_balance: ctx.accounts.buyer_balance.to_account_info(),
+        to_balance: ctx.accounts.seller_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::transfer_balance(
+        cpi_ctx,
+        ctx.accounts.trade_record.buyer,
+        ctx.accounts.trade_record.seller,
+        amount,
+    )?;
+    
+    msg!("Buyer payment moved to seller successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Transfer seller proceeds from vault balance to seller wallet via CPI to vault program
 fn release_seller_collateral_cpi(
     ctx: &Context<SettleTrade>,
     amount: u64,
 ) -> Result<()> {
-    msg!("Releasing seller collateral via CPI: amount: {}", amount);
+    msg!("Releasing seller proceeds to wallet via CPI: amount: {}", amount);
     
     // All accounts from same Context - unified lifetime
     let cpi_accounts = cpi::accounts::TransferOut {
         config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
         user_balance: ctx.accounts.seller_balance.to_account_info(),
         vault_authority: ctx.accounts.vault_authority.to_account_info(),
         vault_token_account: ctx.accounts.vault_ata.to_account_info(),
+        token_mint: ctx.accounts.collateral_mint.to_account_info(),
         recipient_token_account: ctx.accounts.seller_collateral_ata.to_account_info(),
+        outflow_limit: ctx.accounts.outflow_limit.to_account_info(),
         token_program: ctx.accounts.token_program.to_account_info(),
-        instruction_sysvar: ctx.accounts.instruction_sysvar.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
     };
     
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
     let cpi_program = ctx.accounts.vault_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
     
     // Execute CPI call to transfer tokens from vault to seller wallet
     // Note: recipient parameter is the seller's pubkey
     cpi::transfer_out(cpi_ctx, ctx.accounts.seller.key(), amount)?;
     
-    msg!("Seller collateral released successfully via CPI: {}", amount);
+    msg!("Seller proceeds released successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Pay seller protocol fee to treasury via CPI to vault program
+fn pay_seller_fee_cpi(
+    ctx: &Context<SettleTrade>,
+    amount: u64,
+) -> Result<()> {
+    msg!("Paying seller fee to treasury via CPI: amount: {}", amount);
+    
+    let cpi_accounts = cpi::accounts::TransferOut {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        user_balance: ctx.accounts.seller_balance.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        vault_token_account: ctx.accounts.vault_ata.to_account_info(),
+        token_mint: ctx.accounts.collateral_mint.to_account_info(),
+        recipient_token_account: ctx.accounts.treasury_collateral_ata.to_account_info(),
+        outflow_limit: ctx.accounts.outflow_limit.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::transfer_out(cpi_ctx, ctx.accounts.config.treasury, amount)?;
+    
+    msg!("Seller fee paid to treasury successfully via CPI: {}", amount);
+    Ok(())
+}
+
+/// Pay seller reward from the collateral mint reward pool via CPI to vault program
+fn pay_seller_reward_cpi(
+    ctx: &Context<SettleTrade>,
+    amount: u64,
+) -> Result<()> {
+    msg!("Paying seller reward from reward pool via CPI: amount: {}", amount);
+    
+    let reward_pool = ctx.accounts.reward_pool.as_ref()
+        .ok_or(TradingError::RewardPoolNotProvided)?;
+    
+    let cpi_accounts = cpi::accounts::PayReward {
+        config: ctx.accounts.vault_config.to_account_info(),
+        authorized_trader: ctx.accounts.authorized_trader.to_account_info(),
+        reward_pool: reward_pool.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        vault_token_account: ctx.accounts.vault_ata.to_account_info(),
+        token_mint: ctx.accounts.collateral_mint.to_account_info(),
+        recipient_token_account: ctx.accounts.seller_collateral_ata.to_account_info(),
+        outflow_limit: ctx.accounts.outflow_limit.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        caller_signer: ctx.accounts.vault_signer.to_account_info(),
+    };
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SIGNER_SEED, &[ctx.bumps.vault_signer]]];
+    let cpi_program = ctx.accounts.vault_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    
+    cpi::pay_reward(cpi_ctx, ctx.accounts.seller.key(), amount)?;
+    
+    msg!("Seller reward paid successfully via CPI: {}", amount);
     Ok(())
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{CURRENT_ACCOUNT_VERSION, PRICE_SCALE};
+
+    fn trade_record(filled_amount: u64, seller_reward_bps: u16) -> TradeRecord {
+        TradeRecord {
+            trade_id: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            seller: Pubkey::new_unique(),
+            token_id: Pubkey::new_unique(),
+            collateral_mint: Pubkey::new_unique(),
+            filled_amount,
+            price: 3 * PRICE_SCALE / 7,
+            buyer_collateral: 1_001,
+            seller_collateral: 1_303,
+            match_time: 0,
+            settled: false,
+            buyer_fee: 7,
+            seller_fee: 11,
+            settled_amount: 0,
+            rent_payer: Pubkey::new_unique(),
+            settled_at: 0,
+            trade_hash: [0; 32],
+            bump: 255,
+            version: CURRENT_ACCOUNT_VERSION,
+            settlement_deadline: 0,
+            legacy_collateral: false,
+            fill_offset: 0,
+            seller_reward_bps,
+            late_penalty_bps: 0,
+            reserved: [0; 11],
+        }
+    }
+
+    #[test]
+    fn chunked_settlement_releases_exact_totals() {
+        let mut trade_record = trade_record(10_000, 150);
+        let total_reward = 10_000 * (3 * PRICE_SCALE / 7) / PRICE_SCALE * 150 / 10_000;
+        let mut released = (0, 0, 0, 0);
+
+        for chunk in [1, 333, 2_999, 6_666, 1] {
+            let from = trade_record.settled_amount;
+            let (reward, seller_release, buyer_payment, seller_fee) =
+                calculate_settlement_amounts(&trade_record, from, from + chunk).unwrap();
+            released.0 += reward;
+            released.1 += seller_release;
+            released.2 += buyer_payment;
+            released.3 += seller_fee;
+            trade_record.record_settlement(chunk).unwrap();
+        }
+
+        assert!(trade_record.settled);
+        assert_eq!(released, (total_reward, 1_303, 1_001, 11));
+    }
+
+    #[test]
+    fn settlement_reward_uses_the_rate_snapshot() {
+        let trade_record = trade_record(10_000, 0);
+        let (reward, ..) = calculate_settlement_amounts(&trade_record, 0, 10_000).unwrap();
+        assert_eq!(reward, 0);
+    }
+
+    #[test]
+    fn settlement_chunk_cannot_exceed_the_trade() {
+        let trade_record = trade_record(10_000, 150);
+        assert!(calculate_settlement_amounts(&trade_record, 5_000, 10_001).is_err());
+        assert!(calculate_settlement_amounts(&trade_record, 6_000, 5_000).is_err());
+    }
+}
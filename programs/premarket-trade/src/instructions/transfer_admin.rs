@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::TradingError;
+use crate::events::{AdminTransferProposed, AdminTransferAccepted, AdminTransferCancelled};
+
+/// Two-step admin transfer: current admin proposes / cancels
+/// Not gated by pause so a compromised key can be rotated during an incident
+#[derive(Accounts)]
+pub struct ManageAdminTransfer<'info> {
+    /// Trade configuration PDA holding admin and pending admin
+    #[account(
+        mut,
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+    )]
+    pub config: Account<'info, TradeConfig>,
+    
+    /// Current admin signer (must match config.admin)
+    pub admin: Signer<'info>,
+}
+
+/// Two-step admin transfer: pending admin accepts
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// Trade configuration PDA holding admin and pending admin
+    #[account(
+        mut,
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, TradeConfig>,
+    
+    /// Pending admin signer (must match config.pending_admin)
+    pub new_admin: Signer<'info>,
+}
+
+pub fn propose_admin_handler(
+    ctx: Context<ManageAdminTransfer>,
+    new_admin: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    config.propose_admin(new_admin)?;
+    
+    emit!(AdminTransferProposed {
+        admin: config.admin,
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Admin transfer proposed: admin: {} - pending_admin: {}", config.admin, new_admin);
+    
+    Ok(())
+}
+
+pub fn accept_admin_handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let new_admin = ctx.accounts.new_admin.key();
+    
+    let previous_admin = config.accept_admin(new_admin)?;
+    
+    emit!(AdminTransferAccepted {
+        previous_admin,
+        new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Admin transfer accepted: previous_admin: {} - new_admin: {}", previous_admin, new_admin);
+    
+    Ok(())
+}
+
+pub fn cancel_admin_transfer_handler(ctx: Context<ManageAdminTransfer>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    let cancelled_admin = config.cancel_admin_transfer()?;
+    
+    emit!(AdminTransferCancelled {
+        admin: config.admin,
+        cancelled_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Admin transfer cancelled: admin: {} - cancelled_admin: {}", config.admin, cancelled_admin);
+    
+    Ok(())
+}
@@ -5,12 +5,22 @@
  * Allows admin to update economic and technical parameters of the trading system.
  * Critical for system governance and risk management.
  * 
+ * ## ⏳ Timelocked Changes
+ * Economic and technical changes are queued, never applied instantly:
+ * 1. `schedule_*_config`: admin queues a validated config effective >= MIN_CONFIG_CHANGE_DELAY out
+ * 2. `execute_config_change`: anyone applies it once `effective_at` has passed
+ * 3. `cancel_config_change`: admin discards it (also allowed while paused)
+ * 
+ * Traders watch `PendingConfigChange` to react before new ratios / penalties apply.
+ * 
  * ## 🔧 Configuration Types
- * 1. **Economic Config**: Collateral ratios, rewards, penalties, limits
+ * 1. **Economic Config**: Collateral ratios, rewards, penalties, fees, limits
  * 2. **Technical Config**: Settlement time limits, system parameters
+ * 3. **Treasury**: Protocol fee recipient
  * 
  * ## 🛡️ Security Requirements
- * - Only admin can update configurations
+ * - Only admin can schedule / cancel configuration changes
+ * - One queued change per config kind
  * - Parameter validation to prevent invalid settings
  * - Bounds checking for all economic parameters
  * - Event emission for transparency
@@ -19,6 +29,7 @@
  * - Collateral ratios (buyer/seller): 0-200% (0-20000 basis points)
  * - Seller reward: 0-10% (0-1000 basis points)
  * - Late penalty: 0-100% (0-10000 basis points)
+ * - Buyer/seller fees: 0-10% (0-1000 basis points)
  * - Order amount limits: minimum and maximum
  * 
  * ## ⏰ Technical Parameters
@@ -26,36 +37,137 @@
  * - System operational parameters
  * 
  * ## 📈 Event Emission
- * Emits configuration update events for off-chain monitoring
+ * Emits PendingConfigChange / PendingConfigChangeCancelled on queue changes and
+ * configuration update events on execution for off-chain monitoring
  */
 
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::TradingError;
-use crate::events::{EconomicConfigUpdated, TechnicalConfigUpdated};
-use crate::common::{EconomicConfig, TechnicalConfig};
+use crate::events::{
+    EconomicConfigUpdated, PendingConfigChange, PendingConfigChangeCancelled,
+    TechnicalConfigUpdated, TreasuryUpdated,
+};
+use crate::common::{ConfigChange, EconomicConfig, TechnicalConfig};
+
+// Economic config schedule instruction
+#[derive(Accounts)]
+pub struct ScheduleEconomicConfig<'info> {
+    /// Trade configuration PDA
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+    
+    /// Queued change PDA (init fails while another economic change is queued)
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + QueuedConfigChange::INIT_SPACE,
+        seeds = [QueuedConfigChange::QUEUED_CONFIG_CHANGE_SEED, &[ConfigChange::ECONOMIC]],
+        bump,
+    )]
+    pub queued_change: Account<'info, QueuedConfigChange>,
+    
+    /// Admin signer (must be current admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
 
-// Economic config update instruction
+// Technical config schedule instruction
 #[derive(Accounts)]
-pub struct UpdateEconomicConfig<'info> {
+pub struct ScheduleTechnicalConfig<'info> {
+    /// Trade configuration PDA
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+        constraint = !config.paused @ TradingError::TradingPaused,
+    )]
+    pub config: Account<'info, TradeConfig>,
+    
+    /// Queued change PDA (init fails while another technical change is queued)
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + QueuedConfigChange::INIT_SPACE,
+        seeds = [QueuedConfigChange::QUEUED_CONFIG_CHANGE_SEED, &[ConfigChange::TECHNICAL]],
+        bump,
+    )]
+    pub queued_change: Account<'info, QueuedConfigChange>,
+    
+    /// Admin signer (must be current admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Queued config execution instruction (permissionless after the delay)
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
     /// Trade configuration PDA to update
     #[account(
         mut,
         seeds = [TradeConfig::TRADE_CONFIG_SEED],
         bump = config.bump,
-        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
         constraint = !config.paused @ TradingError::TradingPaused,
     )]
     pub config: Account<'info, TradeConfig>,
     
+    /// Queued change PDA (closed on execution)
+    #[account(
+        mut,
+        close = rent_receiver,
+        seeds = [QueuedConfigChange::QUEUED_CONFIG_CHANGE_SEED, &[queued_change.change.kind()]],
+        bump = queued_change.bump,
+    )]
+    pub queued_change: Account<'info, QueuedConfigChange>,
+    
+    /// CHECK: Admin that scheduled the change - receives the rent back
+    #[account(
+        mut,
+        constraint = rent_receiver.key() == queued_change.scheduled_by @ TradingError::InvalidRentPayer,
+    )]
+    pub rent_receiver: UncheckedAccount<'info>,
+    
+    /// Anyone can execute once effective
+    pub executor: Signer<'info>,
+}
+
+// Queued config cancellation instruction
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    /// Trade configuration PDA
+    #[account(
+        seeds = [TradeConfig::TRADE_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ TradingError::InvalidAdmin,
+    )]
+    pub config: Account<'info, TradeConfig>,
+    
+    /// Queued change PDA (closed, rent refunded to admin)
+    #[account(
+        mut,
+        close = admin,
+        seeds = [QueuedConfigChange::QUEUED_CONFIG_CHANGE_SEED, &[queued_change.change.kind()]],
+        bump = queued_change.bump,
+    )]
+    pub queued_change: Account<'info, QueuedConfigChange>,
+    
     /// Admin signer (must be current admin)
     #[account(mut)]
     pub admin: Signer<'info>,
 }
 
-// Technical config update instruction
+// Treasury update instruction
 #[derive(Accounts)]
-pub struct UpdateTechnicalConfig<'info> {
+pub struct UpdateTreasury<'info> {
     /// Trade configuration PDA to update
     #[account(
         mut,
@@ -71,118 +183,200 @@ pub struct UpdateTechnicalConfig<'info> {
     pub admin: Signer<'info>,
 }
 
-/// Update economic configuration parameters
-pub fn update_economic_handler(
-    ctx: Context<UpdateEconomicConfig>,
+/// Queue new economic configuration parameters
+pub fn schedule_economic_handler(
+    ctx: Context<ScheduleEconomicConfig>,
     new_config: EconomicConfig,
+    effective_at: i64,
 ) -> Result<()> {
+    // Step 1: Validate new economic parameters
+    validate_economic_config(&new_config)?;
+    
+    // Step 2: Queue change
+    schedule_change(
+        &mut ctx.accounts.queued_change,
+        ConfigChange::Economic(new_config),
+        ctx.accounts.admin.key(),
+        effective_at,
+        ctx.bumps.queued_change,
+    )
+}
+
+/// Queue new technical configuration parameters
+pub fn schedule_technical_handler(
+    ctx: Context<ScheduleTechnicalConfig>,
+    new_config: TechnicalConfig,
+    effective_at: i64,
+) -> Result<()> {
+    // Step 1: Validate new technical parameters
+    validate_technical_config(&new_config)?;
+    
+    // Step 2: Queue change
+    schedule_change(
+        &mut ctx.accounts.queued_change,
+        ConfigChange::Technical(new_config),
+        ctx.accounts.admin.key(),
+        effective_at,
+        ctx.bumps.queued_change,
+    )
+}
+
+/// Apply a queued configuration change once its delay has elapsed
+pub fn execute_config_change_handler(ctx: Context<ExecuteConfigChange>) -> Result<()> {
     let config = &mut ctx.accounts.config;
+    let queued_change = &ctx.accounts.queued_change;
+    let executor = ctx.accounts.executor.key();
     let current_time = Clock::get()?.unix_timestamp;
     
-    // Step 1: Validate new economic parameters
-    validate_economic_config(&new_config)?;
+    // Step 1: Check timelock
+    require!(
+        queued_change.is_ready(current_time),
+        TradingError::ConfigChangeNotReady
+    );
     
-    // Step 2: Store old config for event
-    let old_config = config.economic_config.clone();
+    // Step 2: Re-validate, apply and emit configuration update event
+    match queued_change.change.clone() {
+        ConfigChange::Economic(new_config) => {
+            validate_economic_config(&new_config)?;
+            
+            let old_config = config.economic_config.clone();
+            config.economic_config = new_config.clone();
+            
+            emit!(EconomicConfigUpdated {
+                admin: queued_change.scheduled_by,
+                executed_by: executor,
+                old_config,
+                new_config,
+                updated_at: current_time,
+            });
+            
+            msg!(
+                "Economic config updated: scheduled by {}, executed by {} at timestamp: {}",
+                queued_change.scheduled_by,
+                executor,
+                current_time
+            );
+        }
+        ConfigChange::Technical(new_config) => {
+            validate_technical_config(&new_config)?;
+            
+            let old_config = config.technical_config.clone();
+            config.technical_config = new_config.clone();
+            
+            emit!(TechnicalConfigUpdated {
+                admin: queued_change.scheduled_by,
+                executed_by: executor,
+                old_config,
+                new_config,
+                updated_at: current_time,
+            });
+            
+            msg!(
+                "Technical config updated: scheduled by {}, executed by {} at timestamp: {}",
+                queued_change.scheduled_by,
+                executor,
+                current_time
+            );
+        }
+        // Market overrides are queued per market - applied by execute_market_config_change
+        ConfigChange::MarketEconomic(_) => {
+            return err!(TradingError::InvalidConfigChangeKind);
+        }
+    }
     
-    // Step 3: Update economic configuration
-    config.economic_config = new_config.clone();
+    Ok(())
+}
+
+/// Discard a queued configuration change
+pub fn cancel_config_change_handler(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let queued_change = &ctx.accounts.queued_change;
+    let current_time = Clock::get()?.unix_timestamp;
     
-    // Step 4: Emit configuration update event
-    emit!(EconomicConfigUpdated {
+    emit!(PendingConfigChangeCancelled {
         admin: ctx.accounts.admin.key(),
-        old_config,
-        new_config,
-        updated_at: current_time,
+        change: queued_change.change.clone(),
+        effective_at: queued_change.effective_at,
+        cancelled_at: current_time,
     });
     
     msg!(
-        "Economic config updated by admin: {} at timestamp: {}",
-        ctx.accounts.admin.key(),
-        current_time
+        "Config change (kind {}) cancelled by admin: {}",
+        queued_change.change.kind(),
+        ctx.accounts.admin.key()
     );
     
     Ok(())
 }
 
-/// Update technical configuration parameters
-pub fn update_technical_handler(
-    ctx: Context<UpdateTechnicalConfig>,
-    new_config: TechnicalConfig,
+/// Update protocol fee recipient
+pub fn update_treasury_handler(
+    ctx: Context<UpdateTreasury>,
+    new_treasury: Pubkey,
 ) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let current_time = Clock::get()?.unix_timestamp;
     
-    // Step 1: Validate new technical parameters
-    validate_technical_config(&new_config)?;
-    
-    // Step 2: Store old config for event
-    let old_config = config.technical_config.clone();
+    let old_treasury = config.treasury;
+    config.update_treasury(new_treasury)?;
     
-    // Step 3: Update technical configuration
-    config.technical_config = new_config.clone();
-    
-    // Step 4: Emit configuration update event
-    emit!(TechnicalConfigUpdated {
+    emit!(TreasuryUpdated {
         admin: ctx.accounts.admin.key(),
-        old_config,
-        new_config,
+        old_treasury,
+        new_treasury,
         updated_at: current_time,
     });
     
     msg!(
-        "Technical config updated by admin: {} at timestamp: {}",
+        "Treasury updated by admin: {} - {} -> {}",
         ctx.accounts.admin.key(),
-        current_time
+        old_treasury,
+        new_treasury
     );
     
     Ok(())
 }
 
-/// Validate economic configuration parameters
-fn validate_economic_config(config: &EconomicConfig) -> Result<()> {
-    // Validate collateral ratios (0-200%)
-    require!(
-        config.buyer_collateral_ratio <= crate::common::MAX_COLLATERAL_RATIO,
-        TradingError::InvalidCollateralRatio
-    );
-    require!(
-        config.seller_collateral_ratio <= crate::common::MAX_COLLATERAL_RATIO,
-        TradingError::InvalidCollateralRatio
-    );
+/// Store queued change and announce it
+fn schedule_change(
+    queued_change: &mut QueuedConfigChange,
+    change: ConfigChange,
+    admin: Pubkey,
+    effective_at: i64,
+    bump: u8,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
     
-    // Validate seller reward (0-10%)
-    require!(
-        config.seller_reward_bps <= crate::common::MAX_REWARD_BPS,
-        TradingError::InvalidRewardParameters
-    );
+    queued_change.initialize(change.clone(), admin, effective_at, current_time, bump)?;
     
-    // Validate late penalty (0-100%)
-    require!(
-        config.late_penalty_bps <= crate::common::MAX_PENALTY_BPS,
-        TradingError::InvalidRewardParameters
-    );
+    emit!(PendingConfigChange {
+        admin,
+        change,
+        scheduled_at: current_time,
+        effective_at,
+    });
     
-    // Validate order amount limits
-    require!(
-        config.minimum_fill_amount > 0,
-        TradingError::ZeroAmount
-    );
-    require!(
-        config.maximum_order_amount > config.minimum_fill_amount,
-        TradingError::InvalidFillAmount
-    );
-    require!(
-        config.maximum_order_amount <= 1_000_000_000_000_000, // 1e15 max
-        TradingError::ExceedOrderAmount
+    msg!(
+        "Config change (kind {}) scheduled by admin: {}, effective at: {}",
+        queued_change.change.kind(),
+        admin,
+        effective_at
     );
     
+    Ok(())
+}
+
+/// Validate economic configuration parameters
+fn validate_economic_config(config: &EconomicConfig) -> Result<()> {
+    config.validate()?;
+    
     msg!(
-        "Economic config validation passed: buyer_ratio: {}, seller_ratio: {}, reward_bps: {}, penalty_bps: {}",
+        "Economic config validation passed: buyer_ratio: {}, seller_ratio: {}, reward_bps: {}, penalty_bps: {}, buyer_fee_bps: {}, seller_fee_bps: {}",
         config.buyer_collateral_ratio,
         config.seller_collateral_ratio,
         config.seller_reward_bps,
-        config.late_penalty_bps
+        config.late_penalty_bps,
+        config.buyer_fee_bps,
+        config.seller_fee_bps
     );
     
     Ok(())
@@ -204,10 +398,17 @@ fn validate_technical_config(config: &TechnicalConfig) -> Result<()> {
         TradingError::InvalidSettleTime
     );
     
+    // Validate trade record retention
+    require!(
+        config.trade_record_retention <= 31_536_000, // At most 1 year
+        TradingError::InvalidRetentionPeriod
+    );
+    
     msg!(
-        "Technical config validation passed: min_settle_time: {}, max_settle_time: {}",
+        "Technical config validation passed: min_settle_time: {}, max_settle_time: {}, trade_record_retention: {}",
         config.min_settle_time,
-        config.max_settle_time
+        config.max_settle_time,
+        config.trade_record_retention
     );
     
     Ok(())
@@ -10,7 +10,7 @@ pub mod error;
 pub mod events;
 pub mod utils;
 
-use common::{PreOrder, EconomicConfig, TechnicalConfig};
+use common::{PreOrder, EconomicConfig, TechnicalConfig, SettlementPayout, RelayerParams, TokenRemapParams};
 
 use instructions::*;
 
@@ -22,10 +22,11 @@ pub mod premarket_trade {
     pub fn initialize_trading(
         ctx: Context<InitializeTrading>,
         vault_program: Pubkey,
+        treasury: Pubkey,
         economic_config: EconomicConfig,
         technical_config: TechnicalConfig,
     ) -> Result<()> {
-        instructions::initialize::handler(ctx, vault_program, economic_config, technical_config)
+        instructions::initialize::handler(ctx, vault_program, treasury, economic_config, technical_config)
     }
 
     /// Create new token market (Admin only)
@@ -35,45 +36,188 @@ pub mod premarket_trade {
         symbol: String,
         name: String,
         settle_time_limit: u32,
+        accepted_collateral: Vec<Pubkey>,
     ) -> Result<()> {
-        instructions::create_token_market::handler(ctx, symbol, name, settle_time_limit)
+        instructions::create_token_market::handler(ctx, symbol, name, settle_time_limit, accepted_collateral)
     }
 
-    /// Map real token to market (Admin only)
+    /// Map real token to market with pre-market → real base unit ratio (Admin only)
     pub fn map_token(
         ctx: Context<MapToken>,
         real_mint: Pubkey,
+        conversion_numerator: u64,
+        conversion_denominator: u64,
     ) -> Result<()> {
-        instructions::map_token::handler(ctx, real_mint)
+        instructions::map_token::handler(ctx, real_mint, conversion_numerator, conversion_denominator)
     }
 
-    /// Update economic parameters (Admin only)
-    pub fn update_economic_config(
-        ctx: Context<UpdateEconomicConfig>,
+    /// Queue a correction of a wrong mapping, only before any trade settled (Admin only)
+    pub fn schedule_token_remap(
+        ctx: Context<ScheduleTokenRemap>,
+        params: TokenRemapParams,
+    ) -> Result<()> {
+        instructions::remap_token::schedule_handler(ctx, params)
+    }
+
+    /// Apply a queued token remap after the delay (Permissionless)
+    pub fn execute_token_remap(ctx: Context<ExecuteTokenRemap>) -> Result<()> {
+        instructions::remap_token::execute_handler(ctx)
+    }
+
+    /// Discard a queued token remap (Admin only)
+    pub fn cancel_token_remap(ctx: Context<CancelTokenRemap>) -> Result<()> {
+        instructions::remap_token::cancel_handler(ctx)
+    }
+
+    /// Require or waive trader Ed25519 order signatures for a market (Admin only)
+    /// Waiving keeps the relayer-trust mode for legacy markets
+    pub fn set_order_signature_mode(
+        ctx: Context<SetOrderSignatureMode>,
+        require_order_signatures: bool,
+    ) -> Result<()> {
+        instructions::set_order_signature_mode::handler(ctx, require_order_signatures)
+    }
+
+    /// Replace accepted collateral mints for a market (Admin only)
+    pub fn set_accepted_collateral(
+        ctx: Context<SetAcceptedCollateral>,
+        accepted_collateral: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_accepted_collateral::handler(ctx, accepted_collateral)
+    }
+
+    /// Queue market-level economic parameters (None = clear), applied after the timelock (Admin only)
+    pub fn schedule_market_economic_config(
+        ctx: Context<ScheduleMarketEconomicConfig>,
+        economic_override: Option<EconomicConfig>,
+        effective_at: i64,
+    ) -> Result<()> {
+        instructions::market_economic_config::schedule_market_config_handler(ctx, economic_override, effective_at)
+    }
+
+    /// Apply a queued market economic override once effective (Permissionless)
+    pub fn execute_market_config_change(ctx: Context<ExecuteMarketConfigChange>) -> Result<()> {
+        instructions::market_economic_config::execute_market_config_handler(ctx)
+    }
+
+    /// Discard a queued market economic override (Admin only)
+    pub fn cancel_market_config_change(ctx: Context<CancelMarketConfigChange>) -> Result<()> {
+        instructions::market_economic_config::cancel_market_config_handler(ctx)
+    }
+
+    /// Open order matching on a market: Listed → Trading (Admin only)
+    pub fn open_trading(ctx: Context<MarketStatusControl>) -> Result<()> {
+        instructions::market_status::open_trading_handler(ctx)
+    }
+
+    /// Halt order matching on a market: Trading → Listed (Admin only)
+    pub fn halt_trading(ctx: Context<MarketStatusControl>) -> Result<()> {
+        instructions::market_status::halt_trading_handler(ctx)
+    }
+
+    /// Open settlement of a mapped market: Listed / Trading → Settlement (Admin only)
+    pub fn enter_settlement(ctx: Context<MarketStatusControl>) -> Result<()> {
+        instructions::market_status::enter_settlement_handler(ctx)
+    }
+
+    /// End settlement: Settlement → Closed (Admin only)
+    pub fn close_market(ctx: Context<MarketStatusControl>) -> Result<()> {
+        instructions::market_status::close_market_handler(ctx)
+    }
+
+    /// Void a cancelled launch: any open status → Voided (Admin only)
+    pub fn void_market(ctx: Context<MarketStatusControl>) -> Result<()> {
+        instructions::market_status::void_market_handler(ctx)
+    }
+
+    /// Queue economic parameters, applied after the timelock (Admin only)
+    pub fn schedule_economic_config(
+        ctx: Context<ScheduleEconomicConfig>,
         new_config: EconomicConfig,
+        effective_at: i64,
     ) -> Result<()> {
-        instructions::update_config::update_economic_handler(ctx, new_config)
+        instructions::update_config::schedule_economic_handler(ctx, new_config, effective_at)
     }
 
-    /// Update technical parameters (Admin only)
-    pub fn update_technical_config(
-        ctx: Context<UpdateTechnicalConfig>,
+    /// Queue technical parameters, applied after the timelock (Admin only)
+    pub fn schedule_technical_config(
+        ctx: Context<ScheduleTechnicalConfig>,
         new_config: TechnicalConfig,
+        effective_at: i64,
+    ) -> Result<()> {
+        instructions::update_config::schedule_technical_handler(ctx, new_config, effective_at)
+    }
+
+    /// Apply a queued config change once effective (Permissionless)
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::update_config::execute_config_change_handler(ctx)
+    }
+
+    /// Discard a queued config change (Admin only)
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::update_config::cancel_config_change_handler(ctx)
+    }
+
+    /// Update protocol fee recipient (Admin only)
+    pub fn update_treasury(
+        ctx: Context<UpdateTreasury>,
+        new_treasury: Pubkey,
     ) -> Result<()> {
-        instructions::update_config::update_technical_handler(ctx, new_config)
+        instructions::update_config::update_treasury_handler(ctx, new_treasury)
     }
 
-    /// Add/remove relayers (Admin only)
-    pub fn manage_relayers(
-        ctx: Context<ManageRelayers>,
+    /// Propose new admin - step 1 of two-step transfer (Admin only)
+    pub fn propose_admin(
+        ctx: Context<ManageAdminTransfer>,
+        new_admin: Pubkey,
+    ) -> Result<()> {
+        instructions::transfer_admin::propose_admin_handler(ctx, new_admin)
+    }
+
+    /// Accept admin role - step 2 of two-step transfer (Pending admin only)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::transfer_admin::accept_admin_handler(ctx)
+    }
+
+    /// Cancel pending admin transfer (Admin only)
+    pub fn cancel_admin_transfer(ctx: Context<ManageAdminTransfer>) -> Result<()> {
+        instructions::transfer_admin::cancel_admin_transfer_handler(ctx)
+    }
+
+    /// Register relayer with permissions, market scope and activity window (Admin only)
+    pub fn add_relayer(
+        ctx: Context<AddRelayer>,
+        relayer: Pubkey,
+        params: RelayerParams,
+    ) -> Result<()> {
+        instructions::manage_relayers::add_relayer_handler(ctx, relayer, params)
+    }
+
+    /// Move a relayer from the legacy TradeConfig list to a RelayerInfo PDA (Admin only)
+    pub fn migrate_legacy_relayer(
+        ctx: Context<MigrateLegacyRelayer>,
         relayer: Pubkey,
-        add: bool,
     ) -> Result<()> {
-        instructions::manage_relayers::handler(ctx, relayer, add)
+        instructions::manage_relayers::migrate_legacy_relayer_handler(ctx, relayer)
+    }
+
+    /// Update relayer permissions, market scope and activity window (Admin only)
+    pub fn update_relayer(
+        ctx: Context<UpdateRelayer>,
+        params: RelayerParams,
+    ) -> Result<()> {
+        instructions::manage_relayers::update_relayer_handler(ctx, params)
+    }
+
+    /// Remove relayer and close its RelayerInfo (Admin only)
+    pub fn remove_relayer(ctx: Context<RemoveRelayer>) -> Result<()> {
+        instructions::manage_relayers::remove_relayer_handler(ctx)
     }
 
     /// **CORE BUSINESS LOGIC**: Match buy and sell orders
-    /// TradeRecord = User-controlled keypair, not PDA
+    /// TradeRecord = PDA ["trade_record", generate_trade_id(buy_hash, sell_hash)]
+    /// Trader signatures must be supplied via Ed25519 precompile instructions
+    /// when the market requires them
     /// Includes CPI calls to vault for collateral locking
     pub fn match_orders(
         ctx: Context<MatchOrders>,
@@ -91,8 +235,27 @@ pub mod premarket_trade {
 
     /// **SETTLEMENT**: Seller delivers tokens to buyer
     /// Includes CPI calls to vault for token transfers
-    pub fn settle_trade(ctx: Context<SettleTrade>) -> Result<()> {
-        instructions::settle_trade::handler(ctx)
+    pub fn settle_trade(
+        ctx: Context<SettleTrade>,
+        payout: SettlementPayout,
+    ) -> Result<()> {
+        instructions::settle_trade::handler(ctx, payout)
+    }
+
+    /// **PARTIAL SETTLEMENT**: Seller delivers part of the tokens to buyer
+    /// Releases pro-rata collateral, payment and reward for the delivered chunk
+    pub fn settle_partial(
+        ctx: Context<SettleTrade>,
+        amount: u64,
+        payout: SettlementPayout,
+    ) -> Result<()> {
+        instructions::settle_trade::settle_partial_handler(ctx, amount, payout)
+    }
+
+    /// **ARCHIVAL**: Close settled/cancelled TradeRecord after retention delay
+    /// Permissionless - rent is refunded to the recorded payer
+    pub fn close_trade_record(ctx: Context<CloseTradeRecord>) -> Result<()> {
+        instructions::close_trade_record::handler(ctx)
     }
 
     /// **CANCELLATION**: Cancel trade after grace period
@@ -101,6 +264,11 @@ pub mod premarket_trade {
         instructions::cancel_trade::handler(ctx)
     }
 
+    /// **REFUND**: Return collateral of a voided market's trade to both sides (Permissionless)
+    pub fn refund_voided_trade(ctx: Context<RefundVoidedTrade>) -> Result<()> {
+        instructions::refund_voided_trade::handler(ctx)
+    }
+
     /// Cancel a pending order and unlock collateral
     pub fn cancel_order(
         ctx: Context<CancelOrder>,
@@ -109,6 +277,14 @@ pub mod premarket_trade {
         instructions::cancel_order::handler(ctx, order)
     }
 
+    /// Invalidate every order of the signer with nonce below `min_nonce`
+    pub fn cancel_all_orders(
+        ctx: Context<CancelAllOrders>,
+        min_nonce: u64,
+    ) -> Result<()> {
+        instructions::cancel_all_orders::handler(ctx, min_nonce)
+    }
+
     /// Emergency pause (Admin only)
     pub fn pause(ctx: Context<EmergencyControl>) -> Result<()> {
         instructions::emergency::pause_handler(ctx)
@@ -118,4 +294,28 @@ pub mod premarket_trade {
     pub fn unpause(ctx: Context<EmergencyControl>) -> Result<()> {
         instructions::emergency::unpause_handler(ctx)
     }
+
+    /// Upgrade TradeConfig to the current layout (Admin only, realloc)
+    pub fn migrate_trade_config(ctx: Context<MigrateTradeConfig>) -> Result<()> {
+        instructions::migrate::migrate_trade_config_handler(ctx)
+    }
+
+    /// Upgrade TokenMarket to the current layout (Admin only, realloc)
+    /// Remaining accounts: vault CollateralMintConfig PDA for each accepted collateral mint
+    pub fn migrate_token_market(
+        ctx: Context<MigrateTokenMarket>,
+        accepted_collateral: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::migrate::migrate_token_market_handler(ctx, accepted_collateral)
+    }
+
+    /// Admin only: upgrade TradeRecord to the current layout (realloc)
+    pub fn migrate_trade_record(ctx: Context<MigrateTradeRecord>) -> Result<()> {
+        instructions::migrate::migrate_trade_record_handler(ctx)
+    }
+
+    /// Permissionless: upgrade OrderStatus to the current layout (realloc)
+    pub fn migrate_order_status(ctx: Context<MigrateOrderStatus>) -> Result<()> {
+        instructions::migrate::migrate_order_status_handler(ctx)
+    }
 } 
\ No newline at end of file
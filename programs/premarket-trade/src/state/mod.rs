@@ -2,8 +2,16 @@ pub mod trade_config;
 pub mod token_market;
 pub mod trade_record;
 pub mod order_status;
+pub mod nonce_state;
+pub mod relayer_info;
+pub mod queued_config_change;
+pub mod queued_token_remap;
 
 pub use trade_config::*;
 pub use token_market::*;
 pub use trade_record::*;
-pub use order_status::*; 
\ No newline at end of file
+pub use order_status::*;
+pub use nonce_state::*;
+pub use relayer_info::*;
+pub use queued_config_change::*;
+pub use queued_token_remap::*; 
\ No newline at end of file
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::error::TradingError;
+
+/// NonceState - Per-trader minimum valid order nonce (PDA)
+/// Seeds: ["nonce_state", trader]
+/// Orders with `nonce < min_nonce` are invalidated ("cancel all below nonce")
+#[account]
+pub struct NonceState {
+    pub trader: Pubkey,                     // Trader wallet (32 bytes)
+    pub min_nonce: u64,                     // Lowest nonce still accepted (8 bytes)
+    pub updated_at: i64,                    // Last time the floor was raised (8 bytes)
+    pub bump: u8,                           // PDA bump (1 byte)
+}
+
+impl NonceState {
+    pub const NONCE_STATE_SEED: &'static [u8] = b"nonce_state";
+    
+    // Account space calculation: fields only (discriminator added at init)
+    pub const INIT_SPACE: usize = 32 + 8 + 8 + 1;
+
+    pub fn initialize(&mut self, trader: Pubkey, bump: u8) {
+        self.trader = trader;
+        self.min_nonce = 0;
+        self.updated_at = 0;
+        self.bump = bump;
+    }
+
+    /// Raise the nonce floor - every order below `new_min_nonce` becomes invalid
+    pub fn raise_min_nonce(&mut self, new_min_nonce: u64, current_time: i64) -> Result<()> {
+        require!(new_min_nonce > self.min_nonce, TradingError::InvalidNonce);
+        
+        self.min_nonce = new_min_nonce;
+        self.updated_at = current_time;
+        Ok(())
+    }
+
+    /// Read the nonce floor from a NonceState PDA that may not exist yet
+    /// An uninitialized account means the trader never cancelled anything (floor = 0)
+    pub fn load_min_nonce(account: &AccountInfo) -> Result<u64> {
+        if account.data_is_empty() {
+            return Ok(0);
+        }
+        
+        require!(account.owner == &crate::ID, TradingError::InvalidAccountOwner);
+        let nonce_state = NonceState::try_deserialize(&mut &account.data.borrow()[..])?;
+        Ok(nonce_state.min_nonce)
+    }
+
+    /// Reject orders whose nonce is below the trader's floor
+    pub fn validate_order_nonce(account: &AccountInfo, nonce: u64) -> Result<()> {
+        let min_nonce = Self::load_min_nonce(account)?;
+        require!(nonce >= min_nonce, TradingError::OrderNonceInvalidated);
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::common::CURRENT_ACCOUNT_VERSION;
 use crate::error::TradingError;
 
 /// OrderStatus - Track individual order state (PDA)
@@ -11,11 +12,13 @@ pub struct OrderStatus {
     pub order_type: OrderType,              // Buy or Sell (1 byte)
     pub original_quantity: u64,             // Original order quantity (8 bytes)
     pub filled_quantity: u64,               // Amount already filled (8 bytes)
-    pub collateral_locked: u64,             // Collateral amount locked (8 bytes)
+    pub collateral_locked: u64,             // Collateral locked into trades by fills (8 bytes)
     pub created_at: i64,                    // Order creation time (8 bytes)
     pub expires_at: i64,                    // Order expiration time (8 bytes)
     pub status: OrderStatusType,            // Current order status (1 byte)
     pub bump: u8,                           // PDA bump (1 byte)
+    pub version: u8,                        // Layout version (1 byte)
+    pub reserved: [u8; 32],                 // Reserved for future fields (32 bytes)
 }
 
 /// Order type enum
@@ -39,7 +42,7 @@ impl OrderStatus {
     pub const ORDER_STATUS_SEED: &'static [u8] = b"order_status";
     
     // Account space calculation: discriminator + fields
-    pub const INIT_SPACE: usize = 32 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 1;
+    pub const INIT_SPACE: usize = 32 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 32;
 
     pub fn initialize(
         &mut self,
@@ -63,6 +66,15 @@ impl OrderStatus {
         self.expires_at = expires_at;
         self.status = OrderStatusType::Active;
         self.bump = bump;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.reserved = [0; 32];
+    }
+
+    /// Upgrade layout version after realloc (migrate_order_status)
+    pub fn upgrade_version(&mut self) -> Result<()> {
+        require!(self.version < CURRENT_ACCOUNT_VERSION, TradingError::AlreadyMigrated);
+        self.version = CURRENT_ACCOUNT_VERSION;
+        Ok(())
     }
 
     /// Get remaining quantity to fill
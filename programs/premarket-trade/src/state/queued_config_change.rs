@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::common::{ConfigChange, CURRENT_ACCOUNT_VERSION, MIN_CONFIG_CHANGE_DELAY};
+use crate::error::TradingError;
+
+/// QueuedConfigChange - Timelocked economic / technical config change (PDA)
+/// Seeds: ["queued_config_change", kind] or ["queued_config_change", kind, token_market]
+/// for market overrides
+/// One pending change per kind (and market) - cancel before scheduling a different one
+#[account]
+pub struct QueuedConfigChange {
+    pub change: ConfigChange,               // New configuration (1 + 29 bytes)
+    pub scheduled_by: Pubkey,               // Admin that queued the change, receives rent back (32 bytes)
+    pub scheduled_at: i64,                  // Queue time (8 bytes)
+    pub effective_at: i64,                  // Executable from this time (8 bytes)
+    pub bump: u8,                           // PDA bump (1 byte)
+    pub version: u8,                        // Layout version (1 byte)
+    pub reserved: [u8; 32],                 // Reserved for future fields (32 bytes)
+}
+
+impl QueuedConfigChange {
+    pub const QUEUED_CONFIG_CHANGE_SEED: &'static [u8] = b"queued_config_change";
+
+    // Account space calculation: fields only (discriminator added at init)
+    // ConfigChange = 1 (variant) + 29 (Option<EconomicConfig>, largest variant)
+    pub const INIT_SPACE: usize = (1 + 29) + 32 + 8 + 8 + 1 + 1 + 32;
+
+    pub fn initialize(
+        &mut self,
+        change: ConfigChange,
+        scheduled_by: Pubkey,
+        effective_at: i64,
+        current_time: i64,
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            effective_at >= current_time.saturating_add(MIN_CONFIG_CHANGE_DELAY),
+            TradingError::ConfigChangeDelayTooShort
+        );
+
+        self.change = change;
+        self.scheduled_by = scheduled_by;
+        self.scheduled_at = current_time;
+        self.effective_at = effective_at;
+        self.bump = bump;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.reserved = [0; 32];
+        Ok(())
+    }
+
+    /// Check timelock has elapsed
+    pub fn is_ready(&self, current_time: i64) -> bool {
+        current_time >= self.effective_at
+    }
+}
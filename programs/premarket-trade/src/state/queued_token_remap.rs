@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::common::{RemapReason, TokenRemapParams, CURRENT_ACCOUNT_VERSION, TOKEN_REMAP_DELAY};
+
+/// QueuedTokenRemap - Timelocked correction of a TokenMarket mapping (PDA)
+/// Seeds: ["queued_token_remap", token_market]
+/// One pending remap per market - cancel before scheduling a different one
+#[account]
+pub struct QueuedTokenRemap {
+    pub token_market: Pubkey,               // Market being corrected (32 bytes)
+    pub real_mint: Pubkey,                  // Corrected real mint (32 bytes)
+    pub conversion_numerator: u64,          // Corrected conversion ratio (8 bytes)
+    pub conversion_denominator: u64,        // (8 bytes)
+    pub reason: RemapReason,                // Reason code (1 byte)
+    pub scheduled_by: Pubkey,               // Admin that queued the remap, receives rent back (32 bytes)
+    pub scheduled_at: i64,                  // Queue time (8 bytes)
+    pub effective_at: i64,                  // Executable from this time (8 bytes)
+    pub bump: u8,                           // PDA bump (1 byte)
+    pub version: u8,                        // Layout version (1 byte)
+    pub reserved: [u8; 32],                 // Reserved for future fields (32 bytes)
+}
+
+impl QueuedTokenRemap {
+    pub const QUEUED_TOKEN_REMAP_SEED: &'static [u8] = b"queued_token_remap";
+
+    // Account space calculation: fields only (discriminator added at init)
+    pub const INIT_SPACE: usize = 32 + 32 + 8 + 8 + 1 + 32 + 8 + 8 + 1 + 1 + 32;
+
+    pub fn initialize(
+        &mut self,
+        token_market: Pubkey,
+        params: &TokenRemapParams,
+        scheduled_by: Pubkey,
+        current_time: i64,
+        bump: u8,
+    ) {
+        self.token_market = token_market;
+        self.real_mint = params.real_mint;
+        self.conversion_numerator = params.conversion_numerator;
+        self.conversion_denominator = params.conversion_denominator;
+        self.reason = params.reason;
+        self.scheduled_by = scheduled_by;
+        self.scheduled_at = current_time;
+        self.effective_at = current_time.saturating_add(TOKEN_REMAP_DELAY);
+        self.bump = bump;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.reserved = [0; 32];
+    }
+
+    /// Check mandatory delay has elapsed
+    pub fn is_ready(&self, current_time: i64) -> bool {
+        current_time >= self.effective_at
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::common::{RelayerParams, CURRENT_ACCOUNT_VERSION};
+use crate::error::TradingError;
+
+/// RelayerInfo - Per-relayer authorization and activity (PDA)
+/// Seeds: ["relayer_info", relayer]
+/// Replaces the capped `TradeConfig.relayers` list (no limit on relayer count)
+#[account]
+pub struct RelayerInfo {
+    pub relayer: Pubkey,                    // Relayer signer (32 bytes)
+    pub permissions: u8,                    // RelayerInfo::PERMISSION_* bitmask (1 byte)
+    pub market: Option<Pubkey>,             // Restrict to one TokenMarket, None = all markets (1 + 32 bytes)
+    pub active_from: i64,                   // Not usable before this time (8 bytes)
+    pub expires_at: i64,                    // Not usable after this time, 0 = no expiry (8 bytes)
+    pub matches_count: u64,                 // Matches executed (8 bytes)
+    pub matched_amount: u64,                // Token amount filled across matches (8 bytes)
+    pub last_used_at: i64,                  // Last authorized operation (8 bytes)
+    pub added_by: Pubkey,                   // Admin that registered the relayer (32 bytes)
+    pub created_at: i64,                    // Registration time (8 bytes)
+    pub bump: u8,                           // PDA bump (1 byte)
+    pub version: u8,                        // Layout version (1 byte)
+    pub reserved: [u8; 32],                 // Reserved for future fields (32 bytes)
+}
+
+impl RelayerInfo {
+    pub const RELAYER_INFO_SEED: &'static [u8] = b"relayer_info";
+    
+    // Permission bits (only bits enforced by an instruction are accepted)
+    pub const PERMISSION_MATCH: u8 = 1 << 0;              // match_orders
+    pub const ALL_PERMISSIONS: u8 = Self::PERMISSION_MATCH;
+    
+    // Account space calculation: fields only (discriminator added at init)
+    pub const INIT_SPACE: usize = 32 + 1 + (1 + 32) + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 1 + 1 + 32;
+
+    pub fn initialize(
+        &mut self,
+        relayer: Pubkey,
+        params: &RelayerParams,
+        added_by: Pubkey,
+        current_time: i64,
+        bump: u8,
+    ) -> Result<()> {
+        self.relayer = relayer;
+        self.matches_count = 0;
+        self.matched_amount = 0;
+        self.last_used_at = 0;
+        self.added_by = added_by;
+        self.created_at = current_time;
+        self.bump = bump;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.reserved = [0; 32];
+        self.update(params)
+    }
+
+    /// Replace permissions, market scope and activity window
+    pub fn update(&mut self, params: &RelayerParams) -> Result<()> {
+        require!(
+            params.permissions != 0 && params.permissions & !Self::ALL_PERMISSIONS == 0,
+            TradingError::InvalidRelayerPermissions
+        );
+        require!(
+            params.expires_at == 0 || params.expires_at > params.active_from,
+            TradingError::InvalidTimeRange
+        );
+        
+        self.permissions = params.permissions;
+        self.market = params.market;
+        self.active_from = params.active_from;
+        self.expires_at = params.expires_at;
+        Ok(())
+    }
+
+    /// Check permission bit
+    pub fn has_permission(&self, permission: u8) -> bool {
+        self.permissions & permission == permission
+    }
+
+    /// Check activity window
+    pub fn is_active(&self, current_time: i64) -> bool {
+        current_time >= self.active_from
+            && (self.expires_at == 0 || current_time <= self.expires_at)
+    }
+
+    /// Validate relayer may perform `permission` on `token_market` now
+    pub fn authorize(&self, permission: u8, token_market: &Pubkey, current_time: i64) -> Result<()> {
+        require!(self.has_permission(permission), TradingError::UnauthorizedRelayer);
+        require!(self.is_active(current_time), TradingError::RelayerNotActive);
+        require!(
+            self.market.is_none() || self.market == Some(*token_market),
+            TradingError::RelayerMarketMismatch
+        );
+        Ok(())
+    }
+
+    /// Record a match executed by this relayer
+    pub fn record_match(&mut self, fill_amount: u64, current_time: i64) -> Result<()> {
+        self.matches_count = self.matches_count
+            .checked_add(1)
+            .ok_or(TradingError::MathOverflow)?;
+        self.matched_amount = self.matched_amount
+            .checked_add(fill_amount)
+            .ok_or(TradingError::MathOverflow)?;
+        self.last_used_at = current_time;
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::common::{MAX_SYMBOL_LENGTH, MAX_NAME_LENGTH};
+use crate::common::{MAX_SYMBOL_LENGTH, MAX_NAME_LENGTH, MAX_ACCEPTED_COLLATERAL, CURRENT_ACCOUNT_VERSION, EconomicConfig, MarketStatus};
 use crate::error::TradingError;
 
 /// TokenMarket - Per-token market data (User-controlled keypair, not PDA)
@@ -13,10 +13,24 @@ pub struct TokenMarket {
     pub mapping_time: Option<i64>,  // When token was mapped
     pub settle_time_limit: u32,     // Grace period in seconds
     pub created_at: i64,            // Creation timestamp
+    pub require_order_signatures: bool, // Verify trader Ed25519 signatures (false = relayer-trust mode)
+    pub accepted_collateral: Vec<Pubkey>, // Collateral mints accepted for matches (max 5)
+    pub version: u8,                // Layout version
+    pub economic_override: Option<EconomicConfig>, // Market-level economics, None = TradeConfig defaults
+    pub status: MarketStatus,       // Lifecycle status (gates matching, settlement and cancellation)
+    pub real_mint_decimals: u8,     // Real mint decimals snapshot at mapping
+    pub conversion_numerator: u64,  // Real base units per `conversion_denominator` pre-market units
+    pub conversion_denominator: u64, // 0 = mapped before conversion ratios (1:1)
+    pub settled_trade_count: u32,   // Trades with at least one delivered chunk (remap only while 0, u32::MAX = unknown)
+    pub settlement_started_at: i64, // When the market entered Settlement (matching stopped), 0 = not yet
+    pub reserved: [u8; 69],         // Reserved for future fields
     // NOTE: No bump field - not a PDA, user-controlled keypair
 }
 
 impl TokenMarket {
+    /// `settled_trade_count` of markets mapped before the counter existed - never remappable
+    pub const UNKNOWN_SETTLED_TRADE_COUNT: u32 = u32::MAX;
+
     // Account space calculation: discriminator + fields
     pub const INIT_SPACE: usize = 8 + // discriminator
         32 + // token_id
@@ -25,7 +39,17 @@ impl TokenMarket {
         1 + 32 + // real_mint (Option<Pubkey>)
         1 + 8 + // mapping_time (Option<i64>)
         4 + // settle_time_limit
-        8; // created_at
+        8 + // created_at
+        1 + // require_order_signatures
+        4 + (32 * MAX_ACCEPTED_COLLATERAL) + // accepted_collateral (Vec<Pubkey>, max 5)
+        1 + // version
+        1 + 28 + // economic_override (Option<EconomicConfig>, carved from reserved)
+        1 + // status (carved from reserved)
+        1 + // real_mint_decimals (carved from reserved)
+        8 + 8 + // conversion_numerator / conversion_denominator (carved from reserved)
+        4 + // settled_trade_count (carved from reserved)
+        8 + // settlement_started_at (carved from reserved)
+        69; // reserved
 
     pub fn initialize(
         &mut self,
@@ -41,23 +65,259 @@ impl TokenMarket {
         self.mapping_time = None;
         self.settle_time_limit = settle_time_limit;
         self.created_at = Clock::get().unwrap().unix_timestamp;
+        self.require_order_signatures = true;
+        self.accepted_collateral = Vec::new();
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.economic_override = None;
+        self.status = MarketStatus::Listed;
+        self.real_mint_decimals = 0;
+        self.conversion_numerator = 0;
+        self.conversion_denominator = 0;
+        self.settled_trade_count = 0;
+        self.settlement_started_at = 0;
+        self.reserved = [0; 69];
+    }
+
+    /// Upgrade layout version after realloc (migrate_token_market)
+    pub fn upgrade_version(&mut self) -> Result<()> {
+        require!(self.version < CURRENT_ACCOUNT_VERSION, TradingError::AlreadyMigrated);
+        self.version = CURRENT_ACCOUNT_VERSION;
+        Ok(())
     }
 
     /// Map real token to this market
-    pub fn map_token(&mut self, real_mint: Pubkey) -> Result<()> {
+    /// `conversion_numerator / conversion_denominator` converts pre-market units to real base units
+    pub fn map_token(
+        &mut self,
+        real_mint: Pubkey,
+        real_mint_decimals: u8,
+        conversion_numerator: u64,
+        conversion_denominator: u64,
+        mapping_time: i64,
+    ) -> Result<()> {
         require!(self.real_mint.is_none(), TradingError::TokenAlreadyMapped);
         
+        self.set_mapping(
+            real_mint,
+            real_mint_decimals,
+            conversion_numerator,
+            conversion_denominator,
+            mapping_time,
+        )
+    }
+
+    /// Correct a wrong mapping - only before any trade settled
+    /// Restarts the settlement window at `mapping_time`, returns previous real mint
+    pub fn remap_token(
+        &mut self,
+        real_mint: Pubkey,
+        real_mint_decimals: u8,
+        conversion_numerator: u64,
+        conversion_denominator: u64,
+        mapping_time: i64,
+    ) -> Result<Pubkey> {
+        let previous_mint = self.real_mint.ok_or(TradingError::TokenNotMapped)?;
+        self.validate_remap()?;
+        
+        self.set_mapping(
+            real_mint,
+            real_mint_decimals,
+            conversion_numerator,
+            conversion_denominator,
+            mapping_time,
+        )?;
+        Ok(previous_mint)
+    }
+
+    /// Check mapping can still be corrected (mapped, open, nothing delivered yet)
+    pub fn validate_remap(&self) -> Result<()> {
+        require!(self.is_mapped(), TradingError::TokenNotMapped);
+        require!(!self.status.is_final(), TradingError::InvalidMarketStatus);
+        require!(self.settled_trade_count == 0, TradingError::MarketHasSettledTrades);
+        Ok(())
+    }
+
+    /// Track first delivery of a trade (blocks remapping from now on)
+    /// Saturates at `UNKNOWN_SETTLED_TRADE_COUNT`
+    pub fn record_settled_trade(&mut self) {
+        self.settled_trade_count = self.settled_trade_count.saturating_add(1);
+    }
+
+    fn set_mapping(
+        &mut self,
+        real_mint: Pubkey,
+        real_mint_decimals: u8,
+        conversion_numerator: u64,
+        conversion_denominator: u64,
+        mapping_time: i64,
+    ) -> Result<()> {
+        require!(
+            conversion_numerator > 0 && conversion_denominator > 0,
+            TradingError::InvalidConversionRatio
+        );
+        
         self.real_mint = Some(real_mint);
-        self.mapping_time = Some(Clock::get()?.unix_timestamp);
+        self.mapping_time = Some(mapping_time);
+        self.real_mint_decimals = real_mint_decimals;
+        self.conversion_numerator = conversion_numerator;
+        self.conversion_denominator = conversion_denominator;
         
         Ok(())
     }
 
+    /// Convert pre-market `amount` to real token base units - rejects lossy rounding
+    pub fn to_real_amount(&self, amount: u64) -> Result<u64> {
+        // Mapped before conversion ratios: pre-market unit == real base unit
+        if self.conversion_denominator == 0 {
+            return Ok(amount);
+        }
+        
+        let scaled = (amount as u128)
+            .checked_mul(self.conversion_numerator as u128)
+            .ok_or(TradingError::MathOverflow)?;
+        require!(
+            scaled % self.conversion_denominator as u128 == 0,
+            TradingError::LossyConversion
+        );
+        
+        self.to_real_amount_floor(amount)
+    }
+
+    /// Real base units to deliver for pre-market units `from..to` of a trade
+    /// Converted on cumulative totals (rounded down), so a chunk never over-delivers
+    /// and the chunk crossing a whole base unit delivers the dust of earlier chunks
+    pub fn real_amount_between(&self, from: u64, to: u64) -> Result<u64> {
+        self.to_real_amount_floor(to)?
+            .checked_sub(self.to_real_amount_floor(from)?)
+            .ok_or(TradingError::MathOverflow.into())
+    }
+
+    /// Convert pre-market `amount` to real token base units, rounding down
+    fn to_real_amount_floor(&self, amount: u64) -> Result<u64> {
+        // Mapped before conversion ratios: pre-market unit == real base unit
+        if self.conversion_denominator == 0 {
+            return Ok(amount);
+        }
+        
+        let scaled = (amount as u128)
+            .checked_mul(self.conversion_numerator as u128)
+            .ok_or(TradingError::MathOverflow)?;
+        
+        u64::try_from(scaled / self.conversion_denominator as u128)
+            .map_err(|_| TradingError::MathOverflow.into())
+    }
+
+    /// Move to `next` status (admin transitions) - returns previous status
+    /// Settlement records when matching stopped; Closed requires the last settlement window to be over
+    pub fn transition(&mut self, next: MarketStatus, current_time: i64) -> Result<MarketStatus> {
+        require!(
+            self.status.can_transition_to(next),
+            TradingError::InvalidMarketStatusTransition
+        );
+        match next {
+            MarketStatus::Settlement => {
+                require!(self.is_mapped(), TradingError::TokenNotMapped);
+                self.settlement_started_at = current_time;
+            }
+            MarketStatus::Closed => {
+                let window_end = self
+                    .settlement_window_end()
+                    .ok_or(TradingError::TokenNotMapped)?;
+                require!(current_time > window_end, TradingError::SettlementWindowOpen);
+            }
+            _ => {}
+        }
+
+        let previous = self.status;
+        self.status = next;
+        Ok(previous)
+    }
+
+    /// Orders can be matched
+    pub fn is_trading(&self) -> bool {
+        self.status == MarketStatus::Trading
+    }
+
+    /// Trades can be settled
+    pub fn is_settling(&self) -> bool {
+        self.status == MarketStatus::Settlement
+    }
+
+    /// Launch cancelled - open trades are refunded without penalty
+    pub fn is_voided(&self) -> bool {
+        self.status == MarketStatus::Voided
+    }
+
+    /// Trades past their deadline can be cancelled by the buyer
+    pub fn allows_trade_cancellation(&self) -> bool {
+        matches!(self.status, MarketStatus::Settlement | MarketStatus::Closed)
+    }
+
+    /// Traders can cancel unfilled orders
+    pub fn allows_order_cancellation(&self) -> bool {
+        !self.status.is_final()
+    }
+
     /// Check if token is mapped
     pub fn is_mapped(&self) -> bool {
         self.real_mint.is_some()
     }
 
+    /// Check if collateral mint is accepted by this market
+    pub fn accepts_collateral(&self, mint: &Pubkey) -> bool {
+        self.accepted_collateral.contains(mint)
+    }
+
+    /// Validate accepted collateral list (non-empty, bounded, no duplicates)
+    pub fn validate_accepted_collateral(mints: &[Pubkey]) -> Result<()> {
+        require!(
+            !mints.is_empty() && mints.len() <= MAX_ACCEPTED_COLLATERAL,
+            TradingError::InvalidAcceptedCollateral
+        );
+        for (i, mint) in mints.iter().enumerate() {
+            require!(
+                !mints[..i].contains(mint),
+                TradingError::InvalidAcceptedCollateral
+            );
+        }
+        Ok(())
+    }
+
+    /// Set / clear market-level economic parameters
+    pub fn set_economic_override(&mut self, economic_override: Option<EconomicConfig>) -> Result<()> {
+        if let Some(config) = &economic_override {
+            config.validate()?;
+        }
+
+        self.economic_override = economic_override;
+        Ok(())
+    }
+
+    /// Resolve economic parameters: market override first, then global `default`
+    pub fn economic_config<'a>(&'a self, default: &'a EconomicConfig) -> &'a EconomicConfig {
+        self.economic_override.as_ref().unwrap_or(default)
+    }
+
+    /// Deadline of the last trade that can still settle: no match happens after
+    /// `settlement_started_at`, so every trade deadline is at or before this one
+    /// None while unmapped
+    pub fn settlement_window_end(&self) -> Option<i64> {
+        self.settlement_deadline(self.settlement_started_at)
+    }
+
+    /// Settlement deadline of a trade matched at `match_time`
+    /// Sellers can only deliver once the token is mapped and the market is in Settlement, so the
+    /// window opens at max(match_time, mapping_time, settlement_started_at) - None while unmapped
+    /// Provisional before Settlement starts (`settlement_started_at` = 0)
+    pub fn settlement_deadline(&self, match_time: i64) -> Option<i64> {
+        self.mapping_time.map(|mapping_time| {
+            match_time
+                .max(mapping_time)
+                .max(self.settlement_started_at)
+                .saturating_add(self.settle_time_limit as i64)
+        })
+    }
+
     /// Get grace period for settlement
     pub fn get_grace_period(&self) -> u32 {
         self.settle_time_limit
@@ -80,4 +340,174 @@ impl TokenMarket {
         );
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTLE_TIME_LIMIT: u32 = 3600;
+
+    fn token_market() -> TokenMarket {
+        TokenMarket {
+            token_id: Pubkey::new_unique(),
+            symbol: "TKN".to_string(),
+            name: "Token".to_string(),
+            real_mint: None,
+            mapping_time: None,
+            settle_time_limit: SETTLE_TIME_LIMIT,
+            created_at: 0,
+            require_order_signatures: true,
+            accepted_collateral: Vec::new(),
+            version: CURRENT_ACCOUNT_VERSION,
+            economic_override: None,
+            status: MarketStatus::Trading,
+            real_mint_decimals: 0,
+            conversion_numerator: 0,
+            conversion_denominator: 0,
+            settled_trade_count: 0,
+            settlement_started_at: 0,
+            reserved: [0; 69],
+        }
+    }
+
+    #[test]
+    fn settlement_deadline_waits_for_mapping() {
+        let mut market = token_market();
+        assert_eq!(market.settlement_deadline(1_000), None);
+
+        market.map_token(Pubkey::new_unique(), 6, 1, 1, 5_000).unwrap();
+        assert_eq!(market.settlement_deadline(1_000), Some(5_000 + SETTLE_TIME_LIMIT as i64));
+        assert_eq!(market.settlement_deadline(6_000), Some(6_000 + SETTLE_TIME_LIMIT as i64));
+    }
+
+    #[test]
+    fn settlement_deadline_restarts_when_settlement_starts_after_mapping() {
+        // Mapped while still Trading - sellers cannot deliver until Settlement
+        let mut market = token_market();
+        market.map_token(Pubkey::new_unique(), 6, 1, 1, 5_000).unwrap();
+        let provisional = market.settlement_deadline(1_000).unwrap();
+
+        market.transition(MarketStatus::Settlement, 100_000).unwrap();
+        assert_eq!(market.settlement_started_at, 100_000);
+
+        let deadline = market.settlement_deadline(1_000).unwrap();
+        assert!(deadline > provisional);
+        // Full window left at the start of Settlement
+        assert_eq!(deadline, 100_000 + SETTLE_TIME_LIMIT as i64);
+    }
+
+    #[test]
+    fn settlement_deadline_follows_remap_during_settlement() {
+        let mut market = token_market();
+        market.map_token(Pubkey::new_unique(), 6, 1, 1, 5_000).unwrap();
+        market.transition(MarketStatus::Settlement, 10_000).unwrap();
+
+        market.remap_token(Pubkey::new_unique(), 9, 1, 1, 20_000).unwrap();
+        assert_eq!(market.settlement_deadline(1_000), Some(20_000 + SETTLE_TIME_LIMIT as i64));
+        assert_eq!(market.settlement_window_end(), Some(20_000 + SETTLE_TIME_LIMIT as i64));
+    }
+
+    #[test]
+    fn close_waits_for_the_window_opened_by_settlement() {
+        // Mapped long before Settlement - the mapping-based window is already over
+        let mut market = token_market();
+        market.map_token(Pubkey::new_unique(), 6, 1, 1, 5_000).unwrap();
+        market.transition(MarketStatus::Settlement, 100_000).unwrap();
+
+        let window_end = 100_000 + SETTLE_TIME_LIMIT as i64;
+        assert_eq!(market.settlement_window_end(), Some(window_end));
+        assert!(market.transition(MarketStatus::Closed, 5_000 + SETTLE_TIME_LIMIT as i64 + 1).is_err());
+        assert!(market.transition(MarketStatus::Closed, window_end).is_err());
+        assert_eq!(market.status, MarketStatus::Settlement);
+
+        assert_eq!(market.transition(MarketStatus::Closed, window_end + 1).unwrap(), MarketStatus::Settlement);
+        assert_eq!(market.status, MarketStatus::Closed);
+    }
+
+    #[test]
+    fn settlement_requires_mapping() {
+        let mut market = token_market();
+        assert!(market.transition(MarketStatus::Settlement, 100_000).is_err());
+        assert_eq!(market.settlement_started_at, 0);
+        assert!(market.transition(MarketStatus::Closed, 100_000).is_err());
+    }
+
+    #[test]
+    fn exact_conversion_rejects_lossy_amounts() {
+        let mut market = token_market();
+        market.map_token(Pubkey::new_unique(), 6, 3, 2, 5_000).unwrap();
+
+        assert_eq!(market.to_real_amount(4).unwrap(), 6);
+        assert!(market.to_real_amount(5).is_err());
+        assert!(market.map_token(Pubkey::new_unique(), 6, 1, 1, 5_000).is_err());
+    }
+
+    #[test]
+    fn unratioed_mapping_converts_one_to_one() {
+        let mut market = token_market();
+        market.real_mint = Some(Pubkey::new_unique());
+        market.mapping_time = Some(5_000);
+
+        assert_eq!(market.to_real_amount(7).unwrap(), 7);
+        assert_eq!(market.real_amount_between(3, 10).unwrap(), 7);
+    }
+
+    #[test]
+    fn chunked_delivery_sums_to_the_exact_total() {
+        // 1 pre-market unit = 2/3 base unit: each chunk floors, the total is exact
+        let mut market = token_market();
+        market.map_token(Pubkey::new_unique(), 6, 2, 3, 5_000).unwrap();
+        let filled_amount = 9;
+        let total = market.to_real_amount(filled_amount).unwrap();
+        assert_eq!(total, 6);
+
+        let mut settled = 0;
+        let mut delivered = 0;
+        for chunk in [1, 1, 2, 4, 1] {
+            let chunk_delivery = market.real_amount_between(settled, settled + chunk).unwrap();
+            // Never ahead of the pro-rata share
+            assert!((delivered + chunk_delivery) * 3 <= (settled + chunk) * 2);
+            settled += chunk;
+            delivered += chunk_delivery;
+        }
+        assert_eq!(settled, filled_amount);
+        assert_eq!(delivered, total);
+    }
+
+    #[test]
+    fn remap_changes_whether_a_trade_converts() {
+        // Trade of 5 units matched before the remap - convertible at 1:1, not at 3:2
+        let mut market = token_market();
+        market.map_token(Pubkey::new_unique(), 6, 1, 1, 5_000).unwrap();
+        assert!(market.to_real_amount(5).is_ok());
+
+        market.remap_token(Pubkey::new_unique(), 9, 3, 2, 6_000).unwrap();
+        assert!(market.to_real_amount(5).is_err());
+    }
+
+    #[test]
+    fn remap_is_blocked_once_a_trade_settled() {
+        let mut market = token_market();
+        assert!(market.validate_remap().is_err());
+
+        market.map_token(Pubkey::new_unique(), 6, 1, 1, 5_000).unwrap();
+        assert!(market.validate_remap().is_ok());
+
+        market.record_settled_trade();
+        assert!(market.remap_token(Pubkey::new_unique(), 6, 1, 1, 6_000).is_err());
+
+        // Legacy markets with an unknown count stay blocked
+        market.settled_trade_count = TokenMarket::UNKNOWN_SETTLED_TRADE_COUNT;
+        market.record_settled_trade();
+        assert_eq!(market.settled_trade_count, TokenMarket::UNKNOWN_SETTLED_TRADE_COUNT);
+        assert!(market.validate_remap().is_err());
+    }
+
+    #[test]
+    fn remap_is_blocked_on_final_markets() {
+        let mut market = token_market();
+        market.map_token(Pubkey::new_unique(), 6, 1, 1, 5_000).unwrap();
+        market.transition(MarketStatus::Voided, 6_000).unwrap();
+        assert!(market.validate_remap().is_err());
+    }
+}
@@ -1,17 +1,27 @@
 use anchor_lang::prelude::*;
-use crate::common::{EconomicConfig, TechnicalConfig};
+use crate::common::{EconomicConfig, TechnicalConfig, CURRENT_ACCOUNT_VERSION};
 use crate::error::TradingError;
 
 /// TradeConfig - Global trading configuration (PDA)
+/// NOTE: Not append-only against the deployed v0 layout (`LegacyTradeConfig`):
+/// `treasury` and the fee / retention config fields shift every later field,
+/// so v0 accounts are rebuilt field by field by `migrate_trade_config`
 #[account]
 pub struct TradeConfig {
     pub admin: Pubkey,                      // Admin authority
     pub vault_program: Pubkey,              // Vault program ID for CPI
-    pub relayers: Vec<Pubkey>,              // Authorized relayers (max 10)
+    pub treasury: Pubkey,                   // Protocol fee recipient (vault balance / ATA owner)
+    pub relayers: Vec<Pubkey>,              // Legacy relayer list - not consulted, drained by migrate_legacy_relayer
     pub economic_config: EconomicConfig,    // Economic parameters
     pub technical_config: TechnicalConfig,  // Technical parameters
     pub paused: bool,                       // Emergency pause
     pub bump: u8,                           // PDA bump
+    pub pending_admin: Option<Pubkey>,      // Proposed admin awaiting acceptance
+    pub version: u8,                        // Layout version
+    pub relayer_count: u32,                 // Registered RelayerInfo PDAs
+    pub legacy_seller_reward_bps: u16,      // Reward rate of pre-upgrade trades, frozen by migrate_trade_config
+    pub legacy_late_penalty_bps: u16,       // Penalty rate of pre-upgrade trades, frozen by migrate_trade_config
+    pub reserved: [u8; 120],                // Reserved for future fields
 }
 
 impl TradeConfig {
@@ -21,27 +31,54 @@ impl TradeConfig {
     pub const INIT_SPACE: usize = 8 + // discriminator
         32 + // admin
         32 + // vault_program
-        4 + (32 * 10) + // relayers (Vec<Pubkey>, max 10)
-        (2 * 4) + (8 * 2) + // economic_config (4 u16 + 2 u64 fields) ✅
-        (4 * 2) + // technical_config (2 u32 fields)
+        32 + // treasury
+        4 + (32 * 10) + // relayers (legacy Vec<Pubkey>, max 10)
+        (2 * 6) + (8 * 2) + // economic_config (6 u16 + 2 u64 fields)
+        (4 * 3) + // technical_config (3 u32 fields)
         1 + // paused
-        1; // bump
+        1 + // bump
+        1 + 32 + // pending_admin (Option<Pubkey>)
+        1 + // version
+        4 + // relayer_count (carved from reserved)
+        2 + 2 + // legacy_seller_reward_bps / legacy_late_penalty_bps (carved from reserved)
+        120; // reserved
 
     pub fn initialize(
         &mut self,
         admin: Pubkey,
         vault_program: Pubkey,
+        treasury: Pubkey,
         economic_config: EconomicConfig,
         technical_config: TechnicalConfig,
         bump: u8,
     ) {
         self.admin = admin;
         self.vault_program = vault_program;
+        self.treasury = treasury;
         self.relayers = Vec::new();
         self.economic_config = economic_config;
         self.technical_config = technical_config;
         self.paused = false;
         self.bump = bump;
+        self.pending_admin = None;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.relayer_count = 0;
+        self.legacy_seller_reward_bps = 0;
+        self.legacy_late_penalty_bps = 0;
+        self.reserved = [0; 120];
+    }
+
+    /// Upgrade layout version after realloc (migrate_trade_config)
+    /// Freezes the pre-upgrade reward / penalty rates: the old program read them at
+    /// settle / cancel time, so they are the rates pre-upgrade trades settle with
+    pub fn upgrade_version(&mut self) -> Result<()> {
+        require!(self.version < CURRENT_ACCOUNT_VERSION, TradingError::AlreadyMigrated);
+        if self.version == 0 {
+            self.legacy_seller_reward_bps = self.economic_config.seller_reward_bps;
+            self.legacy_late_penalty_bps = self.economic_config.late_penalty_bps;
+        }
+        self.version = CURRENT_ACCOUNT_VERSION;
+        Ok(())
     }
 
     /// Check if user is admin
@@ -49,52 +86,75 @@ impl TradeConfig {
         self.admin == *user
     }
 
-    /// Check if user is authorized relayer
-    pub fn is_relayer(&self, user: &Pubkey) -> bool {
-        self.relayers.contains(user)
+    /// Remove relayer from the legacy list (moved to its RelayerInfo PDA)
+    pub fn take_legacy_relayer(&mut self, relayer: &Pubkey) -> Result<()> {
+        let pos = self.relayers
+            .iter()
+            .position(|x| x == relayer)
+            .ok_or(TradingError::UnauthorizedRelayer)?;
+        self.relayers.remove(pos);
+        Ok(())
     }
 
-    /// Add relayer
-    pub fn add_relayer(&mut self, relayer: Pubkey) -> Result<()> {
-        require!(self.relayers.len() < 10, TradingError::TooManyRelayers);
-        require!(!self.relayers.contains(&relayer), TradingError::TooManyRelayers);
-        
-        self.relayers.push(relayer);
+    /// Track RelayerInfo registration
+    pub fn increment_relayer_count(&mut self) -> Result<()> {
+        self.relayer_count = self.relayer_count
+            .checked_add(1)
+            .ok_or(TradingError::MathOverflow)?;
         Ok(())
     }
 
-    /// Remove relayer
-    pub fn remove_relayer(&mut self, relayer: Pubkey) -> Result<()> {
-        if let Some(pos) = self.relayers.iter().position(|x| *x == relayer) {
-            self.relayers.remove(pos);
-            Ok(())
-        } else {
-            Err(TradingError::OrderNotFound.into())
-        }
+    /// Track RelayerInfo removal
+    pub fn decrement_relayer_count(&mut self) {
+        self.relayer_count = self.relayer_count.saturating_sub(1);
     }
 
     /// Update economic config
     pub fn update_economic_config(&mut self, new_config: EconomicConfig) -> Result<()> {
-        // Validate config
-        require!(
-            new_config.buyer_collateral_ratio <= crate::common::MAX_COLLATERAL_RATIO,
-            TradingError::InvalidCollateralRatio
-        );
-        require!(
-            new_config.seller_collateral_ratio <= crate::common::MAX_COLLATERAL_RATIO,
-            TradingError::InvalidCollateralRatio
-        );
+        new_config.validate()?;
+
+        self.economic_config = new_config;
+        Ok(())
+    }
+
+    /// Update protocol fee recipient
+    pub fn update_treasury(&mut self, treasury: Pubkey) -> Result<()> {
+        require!(treasury != Pubkey::default(), TradingError::InvalidTreasury);
+
+        self.treasury = treasury;
+        Ok(())
+    }
+
+    /// Propose new admin (step 1 of admin transfer)
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
         require!(
-            new_config.seller_reward_bps <= crate::common::MAX_REWARD_BPS,
-            TradingError::InvalidRewardParameters
+            new_admin != Pubkey::default() && new_admin != self.admin,
+            TradingError::InvalidPendingAdmin
         );
+
+        self.pending_admin = Some(new_admin);
+        Ok(())
+    }
+
+    /// Accept admin role (step 2 of admin transfer) - returns previous admin
+    pub fn accept_admin(&mut self, new_admin: Pubkey) -> Result<Pubkey> {
         require!(
-            new_config.late_penalty_bps <= crate::common::MAX_PENALTY_BPS,
-            TradingError::InvalidRewardParameters
+            self.pending_admin == Some(new_admin),
+            TradingError::InvalidPendingAdmin
         );
 
-        self.economic_config = new_config;
-        Ok(())
+        let previous_admin = self.admin;
+        self.admin = new_admin;
+        self.pending_admin = None;
+        Ok(previous_admin)
+    }
+
+    /// Cancel pending admin transfer - returns cancelled admin
+    pub fn cancel_admin_transfer(&mut self) -> Result<Pubkey> {
+        let cancelled_admin = self.pending_admin
+            .take()
+            .ok_or(TradingError::NoPendingAdmin)?;
+        Ok(cancelled_admin)
     }
 
     /// Update technical config
@@ -122,4 +182,93 @@ impl TradeConfig {
     pub fn unpause(&mut self) {
         self.paused = false;
     }
-} 
\ No newline at end of file
+}
+
+/// Deployed v0 TradeConfig layout (before protocol fees, versioning and RelayerInfo)
+/// Same account discriminator as TradeConfig - only decodable through this type
+#[derive(AnchorDeserialize)]
+pub struct LegacyTradeConfig {
+    pub admin: Pubkey,
+    pub vault_program: Pubkey,
+    pub relayers: Vec<Pubkey>,
+    pub economic_config: LegacyEconomicConfig,
+    pub technical_config: LegacyTechnicalConfig,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+/// v0 EconomicConfig (no protocol fees)
+#[derive(AnchorDeserialize)]
+pub struct LegacyEconomicConfig {
+    pub minimum_fill_amount: u64,
+    pub maximum_order_amount: u64,
+    pub buyer_collateral_ratio: u16,
+    pub seller_collateral_ratio: u16,
+    pub seller_reward_bps: u16,
+    pub late_penalty_bps: u16,
+}
+
+/// v0 TechnicalConfig (no trade record retention)
+#[derive(AnchorDeserialize)]
+pub struct LegacyTechnicalConfig {
+    pub min_settle_time: u32,
+    pub max_settle_time: u32,
+}
+
+impl LegacyTradeConfig {
+    // Account space of deployed v0 accounts (discriminator included)
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // admin
+        32 + // vault_program
+        4 + (32 * 10) + // relayers (Vec<Pubkey>, max 10)
+        (2 * 4) + (8 * 2) + // economic_config (4 u16 + 2 u64 fields)
+        (4 * 2) + // technical_config (2 u32 fields)
+        1 + // paused
+        1; // bump
+
+    /// Decode v0 account data (discriminator checked against TradeConfig)
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self> {
+        let discriminator = <TradeConfig as anchor_lang::Discriminator>::DISCRIMINATOR;
+        require!(
+            data.len() >= discriminator.len() && data[..discriminator.len()] == discriminator[..],
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        
+        let mut fields = &data[discriminator.len()..];
+        Self::deserialize(&mut fields).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+
+    /// Rebuild as current layout - new fee parameters start at 0, retention at default
+    /// Returned config keeps `version = 0` until `upgrade_version`
+    pub fn into_current(self, treasury: Pubkey) -> TradeConfig {
+        TradeConfig {
+            admin: self.admin,
+            vault_program: self.vault_program,
+            treasury,
+            relayers: self.relayers,
+            economic_config: EconomicConfig {
+                minimum_fill_amount: self.economic_config.minimum_fill_amount,
+                maximum_order_amount: self.economic_config.maximum_order_amount,
+                buyer_collateral_ratio: self.economic_config.buyer_collateral_ratio,
+                seller_collateral_ratio: self.economic_config.seller_collateral_ratio,
+                seller_reward_bps: self.economic_config.seller_reward_bps,
+                late_penalty_bps: self.economic_config.late_penalty_bps,
+                buyer_fee_bps: 0,
+                seller_fee_bps: 0,
+            },
+            technical_config: TechnicalConfig {
+                min_settle_time: self.technical_config.min_settle_time,
+                max_settle_time: self.technical_config.max_settle_time,
+                trade_record_retention: TechnicalConfig::default().trade_record_retention,
+            },
+            paused: self.paused,
+            bump: self.bump,
+            pending_admin: None,
+            version: 0,
+            relayer_count: 0,
+            legacy_seller_reward_bps: 0,
+            legacy_late_penalty_bps: 0,
+            reserved: [0; 120],
+        }
+    }
+}
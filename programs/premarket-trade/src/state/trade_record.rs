@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
-// No common imports needed for this file
+use crate::common::CURRENT_ACCOUNT_VERSION;
 use crate::error::TradingError;
+use crate::state::TokenMarket;
 
-/// TradeRecord - Individual trade record (User-controlled keypair, not PDA)
+/// TradeRecord - Individual trade record (PDA)
+/// Seeds: ["trade_record", generate_trade_id(buy_order_hash, sell_order_hash), fill_offset (u64 LE)]
 /// Exact business requirements mapping
 #[account]
 pub struct TradeRecord {
-    pub trade_id: Pubkey,           // Account address as unique trade ID (EVM compatible naming)
+    pub trade_id: Pubkey,           // PDA address as unique trade ID (EVM compatible naming)
     pub buyer: Pubkey,              // Buyer wallet
     pub seller: Pubkey,             // Seller wallet
     pub token_id: Pubkey,           // TokenMarket account address as token ID (EVM compatible naming)
@@ -17,11 +19,25 @@ pub struct TradeRecord {
     pub seller_collateral: u64,     // Seller collateral locked
     pub match_time: i64,            // When trade was matched
     pub settled: bool,              // Settlement status
-    // pub target_mint: Option<Pubkey>,// Real token mint (after settlement)
-    // NOTE: No bump field - not a PDA, user-controlled keypair
+    pub buyer_fee: u64,             // Protocol fee paid by buyer at match
+    pub seller_fee: u64,            // Protocol fee owed by seller at settlement
+    pub settled_amount: u64,        // Tokens delivered so far (partial settlement)
+    pub rent_payer: Pubkey,         // Relayer that paid rent (refunded on close)
+    pub settled_at: i64,            // When trade was fully settled or cancelled
+    pub trade_hash: [u8; 32],       // generate_trade_id(buy_order_hash, sell_order_hash)
+    pub bump: u8,                   // PDA bump
+    pub version: u8,                // Layout version
+    pub settlement_deadline: i64,   // Deadline seen at match (0 = matched before mapping), rewritten on settle / cancel - see effective_settlement_deadline
+    pub legacy_collateral: bool,    // Matched before the upgrade: collateral was slashed, not locked
+    pub fill_offset: u64,           // Buy order filled_quantity before this fill (PDA seed)
+    pub seller_reward_bps: u16,     // Seller reward rate snapshot at match
+    pub late_penalty_bps: u16,      // Late penalty rate snapshot at match
+    pub reserved: [u8; 11],         // Reserved for future fields
 }
 
 impl TradeRecord {
+    pub const TRADE_RECORD_SEED: &'static [u8] = b"trade_record";
+    
     // Account space calculation: discriminator + fields
     pub const INIT_SPACE: usize = 8 + // discriminator
         32 + // trade_id
@@ -34,8 +50,20 @@ impl TradeRecord {
         8 + // buyer_collateral
         8 + // seller_collateral
         8 + // match_time
-        1; // settled
-        // 1 + 32; // target_mint (Option<Pubkey>)
+        1 + // settled
+        8 + // buyer_fee
+        8 + // seller_fee
+        8 + // settled_amount
+        32 + // rent_payer
+        8 + // settled_at
+        32 + // trade_hash
+        1 + // bump
+        1 + // version
+        8 + // settlement_deadline (carved from reserved)
+        1 + // legacy_collateral (carved from reserved)
+        8 + // fill_offset (carved from reserved)
+        2 + 2 + // seller_reward_bps / late_penalty_bps (carved from reserved)
+        11; // reserved
 
     pub fn initialize(
         &mut self,
@@ -48,6 +76,8 @@ impl TradeRecord {
         price: u64,
         buyer_collateral: u64,
         seller_collateral: u64,
+        buyer_fee: u64,
+        seller_fee: u64,
     ) {
         self.trade_id = trade_id;
         self.buyer = buyer;
@@ -60,7 +90,39 @@ impl TradeRecord {
         self.seller_collateral = seller_collateral;
         self.match_time = Clock::get().unwrap().unix_timestamp;
         self.settled = false;
-        // self.target_mint = None;
+        self.buyer_fee = buyer_fee;
+        self.seller_fee = seller_fee;
+        self.settled_amount = 0;
+        self.rent_payer = Pubkey::default();
+        self.settled_at = 0;
+        self.version = CURRENT_ACCOUNT_VERSION;
+        self.settlement_deadline = 0;
+        self.legacy_collateral = false;
+        self.fill_offset = 0;
+        self.seller_reward_bps = 0;
+        self.late_penalty_bps = 0;
+        self.reserved = [0; 11];
+    }
+
+    /// Upgrade layout version after realloc (migrate_trade_record)
+    pub fn upgrade_version(&mut self) -> Result<()> {
+        require!(self.version < CURRENT_ACCOUNT_VERSION, TradingError::AlreadyMigrated);
+        self.version = CURRENT_ACCOUNT_VERSION;
+        Ok(())
+    }
+
+    /// Derive TradeRecord PDA for a fill of a buy/sell order pair (for clients and tests)
+    /// `fill_offset` is the buy order's `filled_quantity` before the fill
+    pub fn find_address(
+        buy_order_hash: &[u8; 32],
+        sell_order_hash: &[u8; 32],
+        fill_offset: u64,
+    ) -> (Pubkey, u8) {
+        let trade_hash = crate::utils::generate_trade_id(buy_order_hash, sell_order_hash);
+        Pubkey::find_program_address(
+            &[Self::TRADE_RECORD_SEED, &trade_hash, &fill_offset.to_le_bytes()],
+            &crate::ID,
+        )
     }
 
     /// Check if trade is settled
@@ -73,11 +135,57 @@ impl TradeRecord {
         require!(!self.settled, TradingError::TradeAlreadySettled);
         
         self.settled = true;
-        // self.target_mint = Some(target_mint);
         
         Ok(())
     }
 
+    /// Tokens still to be delivered
+    pub fn remaining_amount(&self) -> u64 {
+        self.filled_amount.saturating_sub(self.settled_amount)
+    }
+
+    /// Pro-rata share of `total` for tokens delivered between `from` and `to`
+    /// Computed on cumulative amounts so the final chunk releases any rounding dust
+    pub fn pro_rata_share(&self, total: u64, from: u64, to: u64) -> Result<u64> {
+        require!(from <= to && to <= self.filled_amount, TradingError::InvalidSettlementAmount);
+        if self.filled_amount == 0 {
+            return Ok(0);
+        }
+        
+        let released = |delivered: u64| -> Result<u64> {
+            let share = (total as u128)
+                .checked_mul(delivered as u128)
+                .ok_or(TradingError::MathOverflow)?
+                .checked_div(self.filled_amount as u128)
+                .ok_or(TradingError::MathOverflow)?;
+            Ok(share as u64)
+        };
+        
+        released(to)?
+            .checked_sub(released(from)?)
+            .ok_or(TradingError::MathOverflow.into())
+    }
+
+    /// Record delivered tokens - marks trade settled once fully delivered
+    pub fn record_settlement(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount > 0 && amount <= self.remaining_amount(),
+            TradingError::InvalidSettlementAmount
+        );
+        
+        self.settled_amount = self.settled_amount
+            .checked_add(amount)
+            .ok_or(TradingError::MathOverflow)?;
+        self.settled = self.settled_amount == self.filled_amount;
+        
+        Ok(())
+    }
+
+    /// Check if record can be closed (settled and retention delay elapsed)
+    pub fn can_close(&self, retention: u32, current_time: i64) -> bool {
+        self.settled && current_time >= self.settled_at.saturating_add(retention as i64)
+    }
+
     /// Calculate total trade value
     pub fn total_value(&self) -> Result<u64> {
         self.filled_amount
@@ -90,10 +198,17 @@ impl TradeRecord {
         self.buyer_collateral.saturating_add(self.seller_collateral)
     }
 
-    /// Check if grace period has expired
-    pub fn is_grace_period_expired(&self, grace_period: u32) -> bool {
-        let current_time = Clock::get().unwrap().unix_timestamp;
-        current_time > self.match_time + (grace_period as i64)
+    /// Authoritative settlement deadline: max(match_time, mapping_time, settlement_started_at) + settle_time_limit
+    /// The stored `settlement_deadline` is only a snapshot - it is 0 for trades matched
+    /// before mapping and goes stale when Settlement starts or the market is remapped
+    pub fn effective_settlement_deadline(&self, token_market: &TokenMarket) -> Option<i64> {
+        token_market.settlement_deadline(self.match_time)
+    }
+
+    /// Check if settlement deadline has passed (never before the token is mapped)
+    pub fn is_grace_period_expired(&self, token_market: &TokenMarket, current_time: i64) -> bool {
+        self.effective_settlement_deadline(token_market)
+            .is_some_and(|deadline| current_time > deadline)
     }
 
     /// Validate trade participants
@@ -1,10 +1,18 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use crate::common::{PreOrder, create_order_message};
 use crate::error::TradingError;
 
+// Ed25519 precompile instruction layout (see solana_sdk::ed25519_instruction)
+const ED25519_SIGNATURE_OFFSETS_START: usize = 2;
+const ED25519_SIGNATURE_OFFSETS_SIZE: usize = 14;
+const ED25519_PUBKEY_SIZE: usize = 32;
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
 /// Simplified order validation for relayer-authorized model
-/// Relayer has full authority to match orders - no signature verification needed
-/// This follows industry best practices (Jupiter, Mango, Drift patterns)
+/// Checks order structure only - trader signatures are verified separately
+/// via `verify_order_signature` for markets that require them
 pub fn validate_order_business_logic(
     order: &PreOrder,
     trader: &Pubkey,
@@ -26,6 +34,79 @@ pub fn validate_order_business_logic(
     Ok(())
 }
 
+/// Verify that the transaction carries an Ed25519 precompile instruction
+/// proving `order.trader` signed the exact `create_order_message` bytes
+/// The precompile itself rejects the transaction if any signature is invalid,
+/// so here we only need to find a signature entry over (trader, message)
+pub fn verify_order_signature(
+    instruction_sysvar: &AccountInfo,
+    order: &PreOrder,
+) -> Result<()> {
+    let expected_message = create_order_message(order);
+    let current_index = load_current_index_checked(instruction_sysvar)
+        .map_err(|_| TradingError::InvalidInstructionSysvar)?;
+    
+    // Only instructions executed before this one have been verified by the runtime
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index as usize, instruction_sysvar)
+            .map_err(|_| TradingError::InvalidInstructionSysvar)?;
+        
+        if instruction.program_id != ed25519_program::ID {
+            continue;
+        }
+        
+        if ed25519_instruction_covers(&instruction.data, &order.trader, &expected_message) {
+            msg!("✅ Order signature verified for trader: {}", order.trader);
+            return Ok(());
+        }
+    }
+    
+    Err(TradingError::MissingOrderSignature.into())
+}
+
+/// Check whether an Ed25519 precompile instruction contains a signature
+/// by `signer` over `message`, with all data embedded in the instruction itself
+fn ed25519_instruction_covers(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let num_signatures = match data.first() {
+        Some(count) => *count as usize,
+        None => return false,
+    };
+    
+    for i in 0..num_signatures {
+        let start = ED25519_SIGNATURE_OFFSETS_START + i * ED25519_SIGNATURE_OFFSETS_SIZE;
+        let offsets = match data.get(start..start + ED25519_SIGNATURE_OFFSETS_SIZE) {
+            Some(offsets) => offsets,
+            None => return false,
+        };
+        let read_u16 = |pos: usize| u16::from_le_bytes([offsets[pos], offsets[pos + 1]]);
+        
+        // Signature, public key and message must all live in the precompile instruction,
+        // otherwise the verified bytes could differ from what we compare against
+        let signature_instruction_index = read_u16(2);
+        let public_key_offset = read_u16(4) as usize;
+        let public_key_instruction_index = read_u16(6);
+        let message_offset = read_u16(8) as usize;
+        let message_size = read_u16(10) as usize;
+        let message_instruction_index = read_u16(12);
+        
+        if signature_instruction_index != ED25519_CURRENT_INSTRUCTION
+            || public_key_instruction_index != ED25519_CURRENT_INSTRUCTION
+            || message_instruction_index != ED25519_CURRENT_INSTRUCTION
+        {
+            continue;
+        }
+        
+        let public_key = data.get(public_key_offset..public_key_offset + ED25519_PUBKEY_SIZE);
+        let signed_message = data.get(message_offset..message_offset + message_size);
+        
+        if public_key == Some(signer.as_ref()) && signed_message == Some(message) {
+            return true;
+        }
+    }
+    
+    false
+}
+
 /// Calculate order hash for tracking
 pub fn calculate_order_hash(order: &PreOrder) -> [u8; 32] {
     let message = create_order_message(order);
@@ -100,4 +181,45 @@ pub fn generate_trade_id(buy_hash: &[u8; 32], sell_hash: &[u8; 32]) -> [u8; 32]
     combined.extend_from_slice(buy_hash);
     combined.extend_from_slice(sell_hash);
     anchor_lang::solana_program::hash::hash(&combined).to_bytes()
-} 
\ No newline at end of file
+}
+
+/// Validate collateral mints against vault allowlist entries
+/// Expects one CollateralMintConfig account per mint, in the same order
+pub fn validate_collateral_mint_configs(
+    mints: &[Pubkey],
+    config_accounts: &[AccountInfo],
+    vault_program: &Pubkey,
+) -> Result<()> {
+    use escrow_vault::state::CollateralMintConfig;
+
+    require!(
+        config_accounts.len() >= mints.len(),
+        TradingError::CollateralMintNotAllowed
+    );
+
+    for (mint, account) in mints.iter().zip(config_accounts.iter()) {
+        // Allowlist entries are owned by the vault program
+        require!(
+            account.owner == vault_program,
+            TradingError::InvalidAccountOwner
+        );
+
+        let (expected, _) = Pubkey::find_program_address(
+            &[CollateralMintConfig::COLLATERAL_MINT_CONFIG_SEED, mint.as_ref()],
+            vault_program,
+        );
+        require!(
+            account.key() == expected,
+            TradingError::CollateralMintNotAllowed
+        );
+
+        let data = account.try_borrow_data()?;
+        let mint_config = CollateralMintConfig::try_deserialize(&mut &data[..])?;
+        require!(
+            mint_config.token_mint == *mint && mint_config.enabled,
+            TradingError::CollateralMintNotAllowed
+        );
+    }
+
+    Ok(())
+}
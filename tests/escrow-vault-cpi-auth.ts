@@ -0,0 +1,223 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { EscrowVault } from "../target/types/escrow_vault";
+import { PremarketTrade } from "../target/types/premarket_trade";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// Regression: vault CPI entry points must reject direct calls.
+// Previously the caller was inferred by scanning top-level instructions, so an
+// attacker could call slash_balance / transfer_out directly (optionally next to
+// an authorized program instruction). The caller now has to sign with the
+// ["vault_signer"] PDA of an authorized program, which only that program can do.
+describe("escrow-vault CPI caller authentication", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const vault = anchor.workspace.EscrowVault as Program<EscrowVault>;
+  const premarket = anchor.workspace.PremarketTrade as Program<PremarketTrade>;
+  const connection = provider.connection;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const ALL_OPERATIONS = 0b111111;
+  const DEPOSIT_AMOUNT = 500_000;
+
+  const user = Keypair.generate();
+  const attacker = Keypair.generate();
+
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_config")],
+    vault.programId
+  );
+  const [authorizedTraderPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("authorized_trader"), premarket.programId.toBuffer()],
+    vault.programId
+  );
+  // Signer PDA of premarket-trade - only premarket-trade can sign for it
+  const [premarketVaultSigner] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_signer")],
+    premarket.programId
+  );
+
+  let mint: PublicKey;
+  let userBalancePda: PublicKey;
+  let vaultAuthorityPda: PublicKey;
+  let vaultAta: PublicKey;
+  let attackerAta: PublicKey;
+  let outflowLimitPda: PublicKey;
+
+  const fetchFreeBalance = async () =>
+    (await vault.account.userBalance.fetch(userBalancePda)).balance.toNumber();
+
+  before(async () => {
+    for (const kp of [user, attacker]) {
+      const sig = await connection.requestAirdrop(kp.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(sig, "confirmed");
+    }
+
+    // ✅ Vault config + premarket-trade authorization (idempotent for shared validators)
+    if (!(await connection.getAccountInfo(configPda))) {
+      await vault.methods
+        .initializeVault(admin.publicKey, admin.publicKey)
+        .accounts({ config: configPda, admin: admin.publicKey, systemProgram: SystemProgram.programId })
+        .rpc();
+    }
+    if (!(await connection.getAccountInfo(authorizedTraderPda))) {
+      await vault.methods
+        .addAuthorizedTrader(premarket.programId, ALL_OPERATIONS)
+        .accounts({
+          config: configPda,
+          authorizedTrader: authorizedTraderPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    // ✅ Collateral mint on the allowlist and a user deposit to attack
+    mint = await createMint(connection, admin, admin.publicKey, null, 6);
+    const [collateralMintConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collateral_mint_config"), mint.toBuffer()],
+      vault.programId
+    );
+    await vault.methods
+      .configureCollateralMint(true, new BN(0), new BN(0))
+      .accounts({
+        config: configPda,
+        collateralMintConfig: collateralMintConfigPda,
+        tokenMint: mint,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    [userBalancePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_balance"), user.publicKey.toBuffer(), mint.toBuffer()],
+      vault.programId
+    );
+    [vaultAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_authority"), mint.toBuffer()],
+      vault.programId
+    );
+    [outflowLimitPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("trader_outflow_limit"), premarket.programId.toBuffer(), mint.toBuffer()],
+      vault.programId
+    );
+
+    const userAta = (await getOrCreateAssociatedTokenAccount(connection, admin, mint, user.publicKey)).address;
+    vaultAta = (await getOrCreateAssociatedTokenAccount(connection, admin, mint, vaultAuthorityPda, true)).address;
+    attackerAta = (await getOrCreateAssociatedTokenAccount(connection, admin, mint, attacker.publicKey)).address;
+    await mintTo(connection, admin, mint, userAta, admin, DEPOSIT_AMOUNT);
+
+    await vault.methods
+      .depositCollateral(new BN(DEPOSIT_AMOUNT))
+      .accounts({
+        config: configPda,
+        userBalance: userBalancePda,
+        vaultAuthority: vaultAuthorityPda,
+        collateralMintConfig: collateralMintConfigPda,
+        vaultAta,
+        userAta,
+        tokenMint: mint,
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    expect(await fetchFreeBalance()).to.equal(DEPOSIT_AMOUNT);
+  });
+
+  it("Should reject direct slash_balance signed by an attacker key", async () => {
+    try {
+      await vault.methods
+        .slashBalance(new BN(DEPOSIT_AMOUNT))
+        .accounts({
+          config: configPda,
+          authorizedTrader: authorizedTraderPda,
+          userBalance: userBalancePda,
+          vaultAuthority: vaultAuthorityPda,
+          callerSigner: attacker.publicKey,
+        })
+        .signers([attacker])
+        .rpc();
+
+      expect.fail("Direct slash_balance should have been rejected");
+    } catch (error) {
+      expect(error.toString()).to.include("UnauthorizedTrader");
+    }
+
+    expect(await fetchFreeBalance()).to.equal(DEPOSIT_AMOUNT);
+  });
+
+  it("Should reject direct transfer_out naming the premarket signer PDA without its signature", async () => {
+    // The instruction marks caller_signer as a signer - nobody but premarket-trade
+    // (via invoke_signed) can provide that signature, so the transaction cannot land
+    const ix = await vault.methods
+      .transferOut(attacker.publicKey, new BN(DEPOSIT_AMOUNT))
+      .accounts({
+        config: configPda,
+        authorizedTrader: authorizedTraderPda,
+        userBalance: userBalancePda,
+        vaultAuthority: vaultAuthorityPda,
+        vaultTokenAccount: vaultAta,
+        tokenMint: mint,
+        recipientTokenAccount: attackerAta,
+        outflowLimit: outflowLimitPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        callerSigner: premarketVaultSigner,
+      })
+      .instruction();
+
+    const tx = new anchor.web3.Transaction().add(ix);
+    tx.feePayer = attacker.publicKey;
+    tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+    tx.partialSign(attacker);
+
+    try {
+      await connection.sendRawTransaction(tx.serialize({ requireAllSignatures: false }));
+      expect.fail("Direct transfer_out should have been rejected");
+    } catch (error) {
+      expect(error.toString()).to.match(/signature/i);
+    }
+
+    expect(await fetchFreeBalance()).to.equal(DEPOSIT_AMOUNT);
+  });
+
+  it("Should reject direct transfer_out with the signer flag stripped", async () => {
+    // Hand-built instruction: premarket signer PDA passed as a non-signer account
+    const ix = await vault.methods
+      .transferOut(attacker.publicKey, new BN(DEPOSIT_AMOUNT))
+      .accounts({
+        config: configPda,
+        authorizedTrader: authorizedTraderPda,
+        userBalance: userBalancePda,
+        vaultAuthority: vaultAuthorityPda,
+        vaultTokenAccount: vaultAta,
+        tokenMint: mint,
+        recipientTokenAccount: attackerAta,
+        outflowLimit: outflowLimitPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        callerSigner: premarketVaultSigner,
+      })
+      .instruction();
+    ix.keys = ix.keys.map((meta) =>
+      meta.pubkey.equals(premarketVaultSigner) ? { ...meta, isSigner: false } : meta
+    );
+
+    try {
+      await provider.sendAndConfirm(new anchor.web3.Transaction().add(ix));
+      expect.fail("Unsigned caller_signer should have been rejected");
+    } catch (error) {
+      expect(error.toString()).to.match(/AccountNotSigner|signature|0xbc2/i);
+    }
+
+    expect(await fetchFreeBalance()).to.equal(DEPOSIT_AMOUNT);
+  });
+});